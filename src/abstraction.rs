@@ -12,6 +12,19 @@ pub trait Encryption {
     fn encode(&self, buf: &mut [u8]);
 
     fn decode(&self, buf: &mut [u8]);
+
+    /// Поддерживает ли шифр произвольный доступ к позиции в потоке,
+    /// то есть независимую обработку фрагментов одного файла.
+
+    fn seekable(&self) -> bool;
+
+    /// Шифрование фрагмента, начинающегося со смещения `offset` от начала данных.
+
+    fn encode_at(&self, buf: &mut [u8], offset: usize);
+
+    /// Дешифрование фрагмента, начинающегося со смещения `offset` от начала данных.
+
+    fn decode_at(&self, buf: &mut [u8], offset: usize);
 }
 
 /// Требуется, чтобы секрет можно было представить как срез байтов.
@@ -78,12 +91,65 @@ where
         Self: Sized;
 }
 
+/// Содержимое ресурса, прочитанное целиком.
+///
+/// Большие файлы не копируются в кучу, а остаются отображенными в память страницами.
+
+pub enum Data {
+    Owned(Vec<u8>),
+    Mapped(Box<dyn core::ops::Deref<Target = [u8]> + Send + Sync>),
+}
+
+impl Data {
+    pub fn into_vec(self) -> Vec<u8> {
+        match self {
+            Data::Owned(data) => data,
+            Data::Mapped(map) => map.to_vec(),
+        }
+    }
+}
+
+impl Default for Data {
+    fn default() -> Self {
+        Data::Owned(Vec::new())
+    }
+}
+
+impl From<Vec<u8>> for Data {
+    fn from(data: Vec<u8>) -> Self {
+        Data::Owned(data)
+    }
+}
+
+impl core::ops::Deref for Data {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Data::Owned(data) => data,
+            Data::Mapped(map) => map,
+        }
+    }
+}
+
+impl Debug for Data {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Data::Owned(data) => write!(f, "Owned({} байт)", data.len()),
+            Data::Mapped(map) => write!(f, "Mapped({} байт)", map.len()),
+        }
+    }
+}
+
 /// Данный типаж абстрагирует запись данных с обьекта в буфер
 
 pub trait Reader {
     type Error: core::error::Error + Send + Sync + 'static;
 
-    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+    /// Чтение всех оставшихся данных. Размер ресурса может быть заранее
+    /// неизвестен, как у каналов, поэтому данные читаются до конца.
+
+    fn read_to_end(&mut self) -> Result<Data, Self::Error>;
 }
 
 /// Данный типаж позволяет записывать с буфера в обьект
@@ -92,6 +158,23 @@ pub trait Writer {
     type Error: core::error::Error + Send + Sync + 'static;
 
     fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error>;
+
+    /// Запись `len` байт, которые `fill` заполняет прямо в месте назначения,
+    /// например в отображенных в память страницах файла.
+    ///
+    /// По умолчанию данные собираются в буфере и передаются `write`.
+
+    fn write_with(
+        &mut self,
+        len: usize,
+        fill: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<usize, Self::Error> {
+        let mut buf = vec![0u8; len];
+
+        fill(&mut buf);
+
+        self.write(&buf)
+    }
 }
 
 /// Операции для работы с обьектом
@@ -119,6 +202,159 @@ where
     fn size(&self) -> usize;
 
     fn get_path(&self) -> &Self::Path;
+
+    /// Метаданные ресурса в сериализованном виде: права, время, владелец и т.п.
+    /// Пустой вектор - ресурс не имеет метаданных.
+
+    fn metadata(&self) -> Result<Vec<u8>, Self::Error>;
+
+    /// Восстановление метаданных, ранее полученных из `metadata`.
+
+    fn set_metadata(&self, metadata: &[u8]) -> Result<(), Self::Error>;
+
+    /// Имя ресурса без указания расположения.
+
+    fn name(&self) -> String;
+
+    /// Указывают ли оба пути на один и тот же ресурс, в том числе через разные имена.
+
+    fn same_resource(&self, other: &Self) -> bool;
+
+    /// Проверка, что ресурс можно переименовать в `name`: имя допустимо и не занято.
+    /// Позволяет отказаться от записи до того, как результат окажется на диске.
+
+    fn check_rename(&self, name: &str) -> Result<(), Self::Error>;
+
+    /// Переименование ресурса в пределах того же расположения.
+    /// Имя не может содержать разделителей пути.
+
+    fn rename(&self, name: &str) -> Result<Self, Self::Error>;
+}
+
+/// Параметры отображения расшифрованных данных
+
+#[derive(Debug, Clone, Default)]
+pub struct ViewOptions {
+    /// Лист электронной таблицы по имени или номеру, начиная с 1
+    pub sheet: Option<String>,
+    /// Ограничение числа строк таблицы, 0 - без ограничения
+    pub max_rows: usize,
+    /// Ограничение числа столбцов таблицы, 0 - без ограничения
+    pub max_cols: usize,
+    /// Вывести таблицу в формате CSV вместо выровненного текста
+    pub csv: bool,
+    /// Показать уменьшенное изображение в терминале
+    pub preview: bool,
+    /// Ширина предпросмотра изображения в символах
+    pub preview_width: usize,
+    /// Файл внутри архива, который нужно показать вместо оглавления
+    pub member: Option<String>,
+    /// Путь к одному значению структурированных данных, например `database.password`
+    pub query: Option<String>,
+    /// Подсветка синтаксиса цветами ANSI
+    pub color: bool,
+    /// Показать данные шестнадцатеричным дампом независимо от формата
+    pub hex: bool,
+    /// Смещение начала дампа в байтах
+    pub offset: usize,
+    /// Длина дампа в байтах, `None` - по умолчанию для формата, 0 - до конца данных
+    pub length: Option<usize>,
+}
+
+/// Элемент структурированного представления расшифрованных данных.
+///
+/// Обработчики форматов возвращают последовательность элементов, а интерфейс
+/// сам решает, как их показать: текстом в терминале, в JSON или иначе.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// Заголовок раздела, например `Дешифрованный DOCX` или `Страница 1 из 3`
+    Heading(String),
+    /// Текст, выводимый как есть, в том числе с цветами ANSI
+    Text(String),
+    /// Таблица, первая строка - заголовок
+    Table(Vec<Vec<String>>),
+    /// Пары "название - значение"
+    Fields(Vec<(String, String)>),
+    /// Примечание к выводу, например о показанной части данных
+    Note(String),
+    /// Ошибка разбора данных
+    Error(String),
+}
+
+/// Данный типаж абстрагирует представление данных одного или нескольких форматов
+
+pub trait Renderer<F>: Send + Sync
+where
+    F: ResourceTypeList,
+{
+    /// Строит представление данных формата `format`.
+    /// `renderers` позволяет показать вложенные данные, например файл архива.
+
+    fn render(
+        &self,
+        format: &F,
+        bytes: &[u8],
+        options: &ViewOptions,
+        renderers: &Renderers<F>,
+    ) -> Vec<Block>;
+}
+
+/// Набор обработчиков, зарегистрированных по коду формата.
+///
+/// Формат без своего обработчика и любой формат с `--hex` показывает запасной обработчик.
+
+pub struct Renderers<F>
+where
+    F: ResourceTypeList,
+{
+    renderers: std::collections::HashMap<u16, std::sync::Arc<dyn Renderer<F>>>,
+    fallback: std::sync::Arc<dyn Renderer<F>>,
+}
+
+impl<F> Renderers<F>
+where
+    F: ResourceTypeList,
+{
+    pub fn new(fallback: impl Renderer<F> + 'static) -> Self {
+        Self {
+            renderers: std::collections::HashMap::new(),
+            fallback: std::sync::Arc::new(fallback),
+        }
+    }
+
+    /// Регистрирует обработчик для перечисленных форматов, заменяя прежний.
+
+    pub fn register(&mut self, formats: &[F], renderer: impl Renderer<F> + 'static) -> &mut Self {
+        let renderer: std::sync::Arc<dyn Renderer<F>> = std::sync::Arc::new(renderer);
+
+        for format in formats {
+            self.renderers.insert(format.to_code(), renderer.clone());
+        }
+
+        self
+    }
+
+    /// Есть ли у формата свой обработчик, а не только запасной.
+
+    pub fn contains(&self, format: &F) -> bool {
+        self.renderers.contains_key(&format.to_code())
+    }
+
+    pub fn render(&self, format: &F, bytes: &[u8], options: &ViewOptions) -> Vec<Block> {
+        let renderer = match self.renderers.get(&format.to_code()) {
+            Some(renderer) if !options.hex => renderer,
+            _ => &self.fallback,
+        };
+
+        renderer.render(format, bytes, options, self)
+    }
+
+    /// Представление запасным обработчиком, для данных, которые формат не смог разобрать.
+
+    pub fn render_fallback(&self, format: &F, bytes: &[u8], options: &ViewOptions) -> Vec<Block> {
+        self.fallback.render(format, bytes, options, self)
+    }
 }
 
 pub trait ResourceTypeList
@@ -127,11 +363,36 @@ where
 {
     type Error: core::error::Error + Send + Sync + 'static;
 
-    fn print_function(&self, bytes: &[u8]);
+    /// Обработчики представления всех известных форматов.
 
-    fn to_byte(&self) -> u8;
+    fn renderers() -> Renderers<Self>;
 
-    fn from_byte(byte: u8) -> Result<Self, Self::Error>;
+    /// Представление данных обработчиками по умолчанию.
+
+    fn render(&self, bytes: &[u8], options: &ViewOptions) -> Vec<Block> {
+        Self::renderers().render(self, bytes, options)
+    }
+
+    /// Определение формата по уже прочитанным данным или их начальному окну.
+    /// Не требует повторного чтения ресурса и работает для потоков без позиционирования.
+    /// Имя ресурса уточняет текстовые форматы, неотличимые по содержимому.
+
+    fn detect(bytes: &[u8], name: &str) -> Self;
+
+    /// Формат по имени, короткому имени или расширению, например `pdf` или `docx`.
+
+    fn from_name(name: &str) -> Result<Self, Self::Error>;
+
+    /// Имеет ли смысл сжимать данные этого формата.
+    /// Уже сжатые форматы (архивы, изображения, видео) сжимать бесполезно.
+
+    fn compressible(&self) -> bool;
+
+    /// Стабильный двухбайтовый код формата для заголовка.
+
+    fn to_code(&self) -> u16;
+
+    fn from_code(code: u16) -> Result<Self, Self::Error>;
 }
 
 pub trait EncryptionList
@@ -149,6 +410,40 @@ where
     fn from_byte(byte: u8) -> Result<Self, Self::Error>;
 }
 
+/// Данный типаж абстрагирует сжатие данных перед шифрованием
+
+pub trait CompressionList
+where
+    Self: Sized + Debug + Clone + 'static,
+{
+    type Error: core::error::Error + Send + Sync + 'static;
+
+    fn compress(&self, buf: &[u8]) -> Result<Vec<u8>, Self::Error>;
+
+    fn decompress(&self, buf: &[u8]) -> Result<Vec<u8>, Self::Error>;
+
+    fn to_byte(&self) -> u8;
+
+    fn from_byte(byte: u8) -> Result<Self, Self::Error>;
+}
+
+/// Данный типаж абстрагирует дополнение данных, скрывающее их точный размер
+
+pub trait Padding
+where
+    Self: Sized + Debug + Clone + 'static,
+{
+    /// Размер, до которого дополняются данные длины `len`, не меньше `len`.
+
+    fn padded_len(&self, len: usize) -> usize;
+
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Разбор схемы, записанной `to_bytes`, `None` - запись повреждена
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
 /// Данный типаж абстрагирует работу с обьектом по пути к ресурсу
 
 pub trait UnifiedResourceIdentifierAbstraction: Reader + Writer + std::fmt::Debug {
@@ -161,10 +456,6 @@ pub trait UnifiedResourceIdentifierAbstraction: Reader + Writer + std::fmt::Debu
     fn new(path: Self::Path, op: Operation) -> Result<Self, <Self::Path as ResourcePath>::Error>
     where
         Self: Sized;
-
-    fn path(&mut self) -> &mut Self::Path;
-
-    fn type_resource(&mut self) -> Result<Self::Type, <Self::Type as ResourceTypeList>::Error>;
 }
 
 pub trait NonceProvider: Sized + Debug + Clone + 'static {
@@ -222,12 +513,20 @@ where
 
     fn to_byte(&self) -> [u8; 42];
 
+    /// Полный размер заголовка в файле вместе с разделом метаданных.
+
+    fn size(&self) -> usize;
+
+    /// Раздел метаданных в том виде, в котором он хранится в файле.
+
+    fn get_section(&self) -> &[u8];
+
+    fn set_section(&mut self, section: Vec<u8>);
+
     fn read_from_buffer(buf: &[u8]) -> Result<Self, Self::Error>;
 
     fn write_to_buffer(&mut self, old_buf: &mut [u8], new_buf: &mut [u8]);
 
-    fn remove_from_buffer(&mut self, old_buf: &mut [u8], new_buf: &mut [u8]);
-
     fn get_salt(&self) -> Self::Salt;
 
     fn get_nounce(&self) -> Self::Nonce;
@@ -235,6 +534,8 @@ where
     fn get_cipher(&self) -> Self::Cipher;
 
     fn get_format(&self) -> Self::Format;
+
+    fn get_version(&self) -> u8;
 }
 
 pub trait Router: Reader + Writer {
@@ -248,6 +549,14 @@ pub trait Router: Reader + Writer {
     ) -> Self;
 
     fn resource(&self) -> Result<Self::Resource, <Self as Router>::Error>;
+
+    /// Путь записи результата, если он задан.
+
+    fn out_path(&self) -> Option<&<Self::Resource as UnifiedResourceIdentifierAbstraction>::Path>;
+
+    /// Результат записывается на место читаемого ресурса.
+
+    fn in_place(&self) -> bool;
 }
 
 pub trait Application
@@ -262,7 +571,5 @@ where
 
     type Kdf: KeyDeriver<String, ()>;
 
-    fn new() -> Result<Self, crate::abstraction::error::Error<Self>>;
-
     fn run(&mut self) -> Result<(), crate::abstraction::error::Error<Self>>;
 }
@@ -1,4 +1,4 @@
-use crate::abstraction::Application;
+use clap::Parser;
 
 mod abstraction;
 mod management;
@@ -18,8 +18,46 @@ type Applicat = management::App<
     realisation::derive_key::standard::nonce::StandardNonce,
 >;
 
-fn main() -> Result<(), crate::abstraction::error::Error<Applicat>> {
-    let mut app = Applicat::new()?;
+/// Приложение над ресурсами в памяти для команд, которые шифруют строки, а не файлы
 
-    app.run()
+type MemoryApplicat = management::App<
+    management::router::Router<realisation::object::memory::MemoryResource>,
+    management::scriber::Scriber<
+        realisation::object::file::resource_type::ResourceType,
+        realisation::encryption::CryptoFormat,
+        realisation::derive_key::standard::salt::StandardSalt,
+        realisation::derive_key::standard::nonce::StandardNonce,
+    >,
+    realisation::derive_key::standard::StandardKdf<32>,
+    realisation::object::file::resource_type::ResourceType,
+    realisation::derive_key::standard::salt::StandardSalt,
+    realisation::derive_key::standard::nonce::StandardNonce,
+>;
+
+fn main() -> Result<std::process::ExitCode, Box<dyn core::fmt::Debug>> {
+    let cli = management::interface::cli::Cli::parse();
+
+    match cli.command {
+        management::interface::cli::Command::EncryptText { .. }
+        | management::interface::cli::Command::DecryptText { .. } => MemoryApplicat::execute(cli)
+            .map(|_| std::process::ExitCode::SUCCESS)
+            .map_err(|e| Box::new(e) as Box<dyn core::fmt::Debug>),
+        // Коды возврата как у grep и diff: 0 - есть совпадения или файлы одинаковы,
+        // 1 - нет совпадений или файлы различаются, 2 - ошибка
+        management::interface::cli::Command::Grep { .. }
+        | management::interface::cli::Command::Diff { .. } => match Applicat::execute(cli) {
+            Ok(()) => Ok(std::process::ExitCode::SUCCESS),
+            Err(abstraction::error::Error::Application(
+                management::error::Error::NoMatch | management::error::Error::Differ,
+            )) => Ok(std::process::ExitCode::from(1)),
+            Err(e) => {
+                eprintln!("Error: {:?}", e);
+
+                Ok(std::process::ExitCode::from(2))
+            }
+        },
+        _ => Applicat::execute(cli)
+            .map(|_| std::process::ExitCode::SUCCESS)
+            .map_err(|e| Box::new(e) as Box<dyn core::fmt::Debug>),
+    }
 }
@@ -1,16 +1,38 @@
-use crate::abstraction::Encryption;
+use crate::abstraction::Application;
+use crate::abstraction::CompressionList;
+use crate::abstraction::Padding;
 use crate::abstraction::Secret;
+use crate::realisation::object::memory::{self, path::MEMORY_SCHEME};
 use crate::{
     abstraction::{EncryptionList, ResourcePath, UnifiedResourceIdentifierAbstraction},
-    realisation::encryption::CryptoFormat,
+    realisation::{
+        compression::CompressionFormat, encryption::CryptoFormat, padding::PaddingScheme,
+    },
 };
-use clap::Parser;
+use std::io::{IsTerminal, Write};
 use std::marker::PhantomData;
 
+pub mod armor;
+pub mod auth;
 pub mod error;
 pub mod interface;
+pub mod parity;
+pub mod pool;
 pub mod router;
 pub mod scriber;
+pub mod stego;
+pub mod volume;
+
+/// Имя ресурса в памяти, через который шифруются и расшифровываются строки.
+///
+/// К имени добавляется номер вызова, чтобы параллельные вызовы в одном процессе
+/// не затирали данные друг друга в общем хранилище.
+
+const TEXT_RESOURCE: &str = "text";
+
+/// Номер следующего вызова `execute_text`
+
+static TEXT_CALLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
 
 #[derive(Debug)]
 
@@ -23,17 +45,42 @@ where
     S: crate::abstraction::SaltProvider,
     N: crate::abstraction::NonceProvider,
 {
-    buffer: Vec<u8>,
+    /// Содержимое ресурса: в куче или отображенное в память
+    buffer: crate::abstraction::Data,
     resource: R,
     scriber: M,
     key_deriver: K,
     cli: interface::cli::Cli,
+    section: scriber::section::Section,
+    /// Файл в текстовой обертке: при записи он снова оборачивается
+    armored: bool,
+    /// Процент избыточности Рида-Соломона, 0 - файл без данных восстановления
+    parity: u8,
+    /// Поврежденные фрагменты, восстановленные при чтении
+    repaired: usize,
+    /// Ключ кода аутентификации, выводится вместе с шифром в `cipher`
+    auth_key: [u8; auth::TAG_SIZE],
     _marker_f: std::marker::PhantomData<F>,
     _marker_s: std::marker::PhantomData<S>,
     _marker_n: std::marker::PhantomData<N>,
 }
 
-impl<R, M, K, F, S, N> crate::abstraction::Application for App<R, M, K, F, S, N>
+/// Прочитанный файл после снятия внешних слоев, см. `App::unwrap_layers`
+
+struct Layers {
+    /// Заголовок и зашифрованные данные
+    buf: crate::abstraction::Data,
+    /// Файл был в текстовой обертке
+    armored: bool,
+    /// Процент избыточности, 0 - файл без данных восстановления
+    parity: u8,
+    /// Поврежденные фрагменты, восстановленные при чтении
+    repaired: usize,
+    /// Имя исходного файла, если он собран из томов
+    base: Option<String>,
+}
+
+impl<R, M, K, F, S, N> App<R, M, K, F, S, N>
 where
     F: crate::abstraction::ResourceTypeList,
     R: crate::abstraction::Router<
@@ -44,132 +91,334 @@ where
     N: crate::abstraction::NonceProvider,
     K: crate::abstraction::KeyDeriver<String, (), Salt = S, Nonce = N>,
 {
-    type Error = error::Error;
+    /// Выполнение команды над всеми переданными файлами.
+    ///
+    /// Каждый файл обрабатывается отдельным приложением в пуле из `--jobs` потоков.
 
-    type Router = R;
+    pub fn execute(cli: interface::cli::Cli) -> Result<(), crate::abstraction::error::Error<Self>> {
+        if let interface::cli::Command::EncryptText { .. }
+        | interface::cli::Command::DecryptText { .. } = cli.command
+        {
+            return Self::execute_text(cli);
+        }
 
-    type Scriber = M;
+        let jobs = cli.jobs();
 
-    type Kdf = K;
+        let tasks = cli
+            .split()
+            .map_err(crate::abstraction::error::Error::Application)?;
 
-    fn new() -> Result<Self, crate::abstraction::error::Error<Self>> {
-        let cli = interface::cli::Cli::parse();
+        let total = tasks.len();
 
-        match &cli.command {
-            interface::cli::Command::Read { path, password } => {
-                // 1. Инициализация пути и роутера
-                let resource_path = <<Self::Router as crate::abstraction::Router>::Resource as UnifiedResourceIdentifierAbstraction>::Path::new(
-                    path.to_string(),
-                    crate::abstraction::Operation::Open
-                ).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
-
-                // Создаем ресурс для получения размера
-                let mut resource = <Self::Router as crate::abstraction::Router>::Resource::new(
-                    resource_path.clone(),
-                    crate::abstraction::Operation::Open,
-                )
-                .map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
+        let missed = std::sync::atomic::AtomicUsize::new(0);
 
-                let file_size: usize = resource.path().size();
+        // Как у grep: файл без совпадений не ошибка, пока совпадения есть в других
+        pool::for_each(tasks, jobs, |cli| match Self::with_cli(cli)?.run() {
+            Err(crate::abstraction::error::Error::Application(error::Error::NoMatch)) => {
+                missed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
-                // Создаем Роутер для операций чтения
-                let mut router: Self::Router =
-                    <Self::Router as crate::abstraction::Router>::new(resource_path, None);
+                Ok(())
+            }
+            result => result,
+        })?;
 
-                // 2. ЧТЕНИЕ ВСЕГО ФАЙЛА В БУФЕР (Операционный шаг 1)
-                let mut buf: Vec<u8> = vec![0u8; file_size];
+        if total > 0 && missed.into_inner() == total {
+            return Err(crate::abstraction::error::Error::Application(
+                error::Error::NoMatch,
+            ));
+        }
 
-                router
-                    .read(&mut buf)
-                    .map_err(|e| crate::abstraction::error::Error::<Self>::ReaderError(e))?;
+        Ok(())
+    }
 
-                // 3. ИЗВЛЕЧЕНИЕ ЗАГОЛОВКА (Scriber)
-                let scriber: Self::Scriber =
-                    <Self::Scriber as crate::abstraction::Header>::read_from_buffer(&buf)
-                        .map_err(|e| crate::abstraction::error::Error::<Self>::HeaderError(e))?;
+    /// Шифрование и расшифровка строки как файла в памяти `mem://`.
+    ///
+    /// Строка обрабатывается командой `prepare --armor` или `decrypt`, поэтому результат
+    /// совпадает по формату с зашифрованным файлом. Приложение должно работать
+    /// с ресурсами в памяти (`MemoryResource`).
 
-                // 4. ГЕНЕРАЦИЯ КЛЮЧА
-                let salt = scriber.get_salt();
+    fn execute_text(
+        cli: interface::cli::Cli,
+    ) -> Result<(), crate::abstraction::error::Error<Self>> {
+        let name = format!(
+            "{}-{}",
+            TEXT_RESOURCE,
+            TEXT_CALLS.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        );
 
-                let nonce = scriber.get_nounce();
+        let path = format!("{}{}", MEMORY_SCHEME, name);
 
-                let secret_password: <<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Secret = <<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Secret::new(password.clone()); // Пароль как Secret
-                let params: <<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Params = <<<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Params as core::default::Default>::default(); // Предполагаем Default для параметров
-                let derive_key =
-                    <<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf>::new(
-                        secret_password,
-                        params,
-                        salt,
-                        nonce,
-                    );
+        let (text, command) = match cli.command {
+            interface::cli::Command::EncryptText {
+                text,
+                password,
+                cipher,
+                pad,
+            } => (
+                text,
+                interface::cli::Command::Prepare {
+                    path_inner: vec![path],
+                    path_outer: None,
+                    password,
+                    cipher,
+                    format: None,
+                    compress: None,
+                    pad,
+                    obfuscate_name: false,
+                    armor: true,
+                    split: None,
+                    parity: None,
+                    carrier: None,
+                },
+            ),
+            interface::cli::Command::DecryptText { text, password } => (
+                text,
+                interface::cli::Command::Decrypt {
+                    path_inner: vec![path],
+                    path_outer: None,
+                    password,
+                    preserve: false,
+                    no_preserve: true,
+                    restore_name: false,
+                    carrier: false,
+                },
+            ),
+            command => {
+                return Self::with_cli(interface::cli::Cli { command, ..cli })?.run();
+            }
+        };
 
-                // 6. ФИНАЛЬНАЯ СБОРКА ПРИЛОЖЕНИЯ
-                Ok(App {
-                    buffer: buf,             // Считанные данные
-                    resource: router,        // Роутер
-                    scriber: scriber,        // Прочитанный заголовок
-                    key_deriver: derive_key, // KDF генератор ключей
-                    cli: cli,
-                    _marker_f: PhantomData::default(),
-                    _marker_n: PhantomData::default(),
-                    _marker_s: PhantomData::default(),
-                })
+        let encrypt = matches!(command, interface::cli::Command::Prepare { .. });
+
+        let input = match text {
+            Some(text) => text.into_bytes(),
+            None => {
+                let mut input = Vec::new();
+
+                std::io::Read::read_to_end(&mut std::io::stdin(), &mut input).map_err(|e| {
+                    crate::abstraction::error::Error::Application(error::Error::Input(e))
+                })?;
+
+                // Перевод строки после `echo` или ввода с клавиатуры не входит в секрет
+                if encrypt && input.ends_with(b"\n") {
+                    input.pop();
+
+                    if input.ends_with(b"\r") {
+                        input.pop();
+                    }
+                }
+
+                input
+            }
+        };
+
+        memory::path::put(&name, input);
+
+        let result =
+            Self::with_cli(interface::cli::Cli { command, ..cli }).and_then(|mut app| app.run());
+
+        let output = memory::path::take(&name).unwrap_or_default();
+
+        result?;
+
+        let mut stdout = std::io::stdout().lock();
+
+        // Расшифрованная строка без перевода строки не должна сливаться с приглашением терминала
+        let newline = !encrypt && !output.ends_with(b"\n") && stdout.is_terminal();
+
+        stdout
+            .write_all(&output)
+            .and_then(|_| {
+                if newline {
+                    stdout.write_all(b"\n")
+                } else {
+                    Ok(())
+                }
+            })
+            .map_err(|e| crate::abstraction::error::Error::Application(error::Error::Output(e)))
+    }
+
+    /// Сборка приложения по уже разобранным аргументам командной строки.
+    ///
+    /// Позволяет запускать конвейер внутри процесса, например с ресурсами в памяти.
+
+    pub fn with_cli(
+        cli: interface::cli::Cli,
+    ) -> Result<Self, crate::abstraction::error::Error<Self>> {
+        match &cli.command {
+            interface::cli::Command::Read { path, password, .. }
+            | interface::cli::Command::Edit { path, password, .. }
+            | interface::cli::Command::Diff {
+                a: path, password, ..
+            }
+            | interface::cli::Command::Info { path, password } => {
+                let (path, password) = (path.clone(), password.clone());
+
+                Self::open(cli, path, password)
             }
+            interface::cli::Command::EncryptText { .. }
+            | interface::cli::Command::DecryptText { .. } => Err(
+                crate::abstraction::error::Error::Application(error::Error::NotFileCommand),
+            ),
+            interface::cli::Command::Hide {
+                path,
+                carrier,
+                path_outer,
+                password,
+                cipher,
+                pad,
+            } => {
+                // Файл шифруется обычным конвейером prepare, результат встраивается в контейнер
+                let command = interface::cli::Command::Prepare {
+                    path_inner: vec![path.clone()],
+                    path_outer: Some(path_outer.clone().unwrap_or_else(|| carrier.clone())),
+                    password: password.clone(),
+                    cipher: cipher.clone(),
+                    format: None,
+                    compress: None,
+                    pad: *pad,
+                    obfuscate_name: false,
+                    armor: false,
+                    split: None,
+                    parity: None,
+                    carrier: Some(carrier.clone()),
+                };
+
+                Self::with_cli(interface::cli::Cli { command, ..cli })
+            }
+            interface::cli::Command::Reveal {
+                carrier,
+                path_outer,
+                password,
+            } => {
+                // Без пути записи файл получает исходное имя из заголовка рядом с контейнером
+                let command = interface::cli::Command::Decrypt {
+                    path_inner: vec![carrier.clone()],
+                    path_outer: Some(
+                        path_outer
+                            .clone()
+                            .unwrap_or_else(|| format!("{}.revealed", carrier)),
+                    ),
+                    password: password.clone(),
+                    preserve: false,
+                    no_preserve: false,
+                    restore_name: path_outer.is_none(),
+                    carrier: true,
+                };
+
+                Self::with_cli(interface::cli::Cli { command, ..cli })
+            }
+            interface::cli::Command::Repair { paths } => match paths.as_slice() {
+                [path] => {
+                    let path = path.clone();
+
+                    // Данные восстановления читаются без пароля
+                    Self::open(cli, path, String::new())
+                }
+                _ => Err(crate::abstraction::error::Error::Application(
+                    error::Error::ManyResources,
+                )),
+            },
+            interface::cli::Command::Grep {
+                paths, password, ..
+            } => match paths.as_slice() {
+                [path] => {
+                    let (path, password) = (path.clone(), password.clone());
+
+                    Self::open(cli, path, password)
+                }
+                _ => Err(crate::abstraction::error::Error::Application(
+                    error::Error::ManyResources,
+                )),
+            },
             interface::cli::Command::Prepare {
                 path_inner,
                 path_outer,
                 password,
                 cipher,
+                format,
+                compress,
+                armor,
+                parity,
+                ..
             } => {
+                let armored = *armor;
+
+                let parity = parity.unwrap_or(0);
+
+                let path_inner = match path_inner.as_slice() {
+                    [path] => path,
+                    _ => {
+                        return Err(crate::abstraction::error::Error::Application(
+                            error::Error::ManyResources,
+                        ));
+                    }
+                };
+
                 // 1. Инициализация путей и роутера
-                let path_inner: <<Self::Router as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path = <<Self::Router as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path::new(path_inner.to_string(), crate::abstraction::Operation::Open).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
+                let path_inner: <<R as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path = <<R as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path::new(path_inner.to_string(), crate::abstraction::Operation::Open).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
 
                 let path_outer: Option<<<R as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path> = match path_outer {
                     Some(path) => {
-                     Some(<<Self::Router as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path::new(path.to_string(), crate::abstraction::Operation::Create).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?)
+                     Some(<<R as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path::new(path.to_string(), crate::abstraction::Operation::Create).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?)
                     },
                     None => Some(path_inner.clone()),
                 };
 
-                let mut resource_inner: <Self::Router as crate::abstraction::Router>::Resource =
-                    <Self::Router as crate::abstraction::Router>::Resource::new(
-                        path_inner,
-                        crate::abstraction::Operation::Open,
-                    )
-                    .map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
+                // Создаем Роутер для операций чтения. Ресурс открывается один раз
+                // при чтении: повторное открытие канала ждало бы нового писателя
+                let mut router: R =
+                    <R as crate::abstraction::Router>::new(path_inner.clone(), path_outer);
 
-                let size: usize = resource_inner.path().size();
+                // 2. Чтение файла целиком, большие файлы остаются отображенными в память
+                let buf = router
+                    .read_to_end()
+                    .map_err(|e| crate::abstraction::error::Error::<Self>::ReaderError(e))?;
 
-                let mut buf: Vec<u8> = vec![0u8; size];
+                let salt = <K as crate::abstraction::KeyDeriver<String, ()>>::Salt::generate()
+                    .map_err(|e| crate::abstraction::error::Error::SaltError(e))?;
 
-                // Создаем Роутер для операций чтения
-                let mut router: Self::Router = <Self::Router as crate::abstraction::Router>::new(
-                    resource_inner.path().clone(),
-                    path_outer,
-                );
+                let nonce = <K as crate::abstraction::KeyDeriver<String, ()>>::Nonce::generate(
+                    &password,
+                    salt.as_bytes(),
+                )
+                .map_err(|e| crate::abstraction::error::Error::NonceError(e))?;
 
-                // 2. Чтение файла в буфер
-                router
-                    .read(&mut buf)
-                    .map_err(|e| crate::abstraction::error::Error::<Self>::ReaderError(e))?;
+                // Формат определяется по уже прочитанному буферу, повторное чтение
+                // ресурса сдвигало бы позицию и не работало бы для каналов
+                let format = match format {
+                    Some(name) => F::from_name(name)
+                        .map_err(|e| crate::abstraction::error::Error::FormatListError(e))?,
+                    None => F::detect(&buf, &path_inner.name()),
+                };
+
+                // Метаданные исходного файла сохраняются в зашифрованном разделе заголовка
+                let mut section = scriber::section::Section::default();
+
+                let metadata = path_inner
+                    .metadata()
+                    .map_err(|e| crate::abstraction::error::Error::ResourcePathError(e))?;
+
+                if !metadata.is_empty() {
+                    section.insert(scriber::section::tag::METADATA, metadata);
+                }
 
-                let salt =
-                    <Self::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Salt::generate()
-                        .map_err(|e| crate::abstraction::error::Error::SaltError(e))?;
+                section.insert(scriber::section::tag::NAME, path_inner.name().into_bytes());
 
-                let nonce =
-                    <Self::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Nonce::generate(
-                        &password,
-                        salt.as_bytes(),
-                    )
-                    .map_err(|e| crate::abstraction::error::Error::NonceError(e))?;
+                // Уже сжатые форматы повторно не сжимаются
+                let compression = compress.unwrap_or(if format.compressible() {
+                    CompressionFormat::Zstd
+                } else {
+                    CompressionFormat::None
+                });
 
-                let format = resource_inner
-                    .type_resource()
-                    .map_err(|e| crate::abstraction::error::Error::FormatListError(e))?;
+                section.insert(
+                    scriber::section::tag::COMPRESSION,
+                    vec![compression.to_byte()],
+                );
 
                 // 3. Создание подписи
-                let scriber: Self::Scriber = <Self::Scriber as crate::abstraction::Header>::new(
+                let scriber: M = <M as crate::abstraction::Header>::new(
                     format,
                     cipher.clone(),
                     salt.clone(),
@@ -192,6 +441,11 @@ where
                     scriber: scriber,
                     key_deriver: derive_key,
                     cli: cli,
+                    section,
+                    armored,
+                    parity,
+                    repaired: 0,
+                    auth_key: [0; auth::TAG_SIZE],
                     _marker_f: PhantomData::default(),
                     _marker_n: PhantomData::default(),
                     _marker_s: PhantomData::default(),
@@ -201,43 +455,68 @@ where
                 path_inner,
                 path_outer,
                 password,
+                ..
             } => {
+                let path_inner = match path_inner.as_slice() {
+                    [path] => path,
+                    _ => {
+                        return Err(crate::abstraction::error::Error::Application(
+                            error::Error::ManyResources,
+                        ));
+                    }
+                };
+
+                let path_name = path_inner.clone();
+
                 // 1. Инициализация путей и роутера
-                let path_inner: <<Self::Router as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path = <<Self::Router as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path::new(path_inner.to_string(), crate::abstraction::Operation::Open).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
+                let path_inner: <<R as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path = <<R as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path::new(path_inner.to_string(), crate::abstraction::Operation::Open).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
 
                 let path_outer: Option<<<R as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path> = match path_outer {
                     Some(path) => {
-                     Some(<<Self::Router as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path::new(path.to_string(), crate::abstraction::Operation::Create).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?)
+                     Some(<<R as crate::abstraction::Router>::Resource as crate::abstraction::UnifiedResourceIdentifierAbstraction>::Path::new(path.to_string(), crate::abstraction::Operation::Create).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?)
                     },
-                    None => Some(path_inner.clone()),
+                    None => None,
                 };
 
-                let mut resource_inner: <Self::Router as crate::abstraction::Router>::Resource =
-                    <Self::Router as crate::abstraction::Router>::Resource::new(
-                        path_inner,
-                        crate::abstraction::Operation::Open,
-                    )
-                    .map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
+                // Создаем Роутер для операций чтения
+                let mut router: R =
+                    <R as crate::abstraction::Router>::new(path_inner.clone(), None);
 
-                let size: usize = resource_inner.path().size();
+                // 2. ЧТЕНИЕ ВСЕГО ФАЙЛА (Операционный шаг 1)
+                let buf = router
+                    .read_to_end()
+                    .map_err(|e| crate::abstraction::error::Error::<Self>::ReaderError(e))?;
 
-                let mut buf: Vec<u8> = vec![0u8; size];
+                let Layers {
+                    mut buf,
+                    armored,
+                    parity,
+                    repaired,
+                    base,
+                } = Self::unwrap_layers(&cli.command, &path_name, buf)?;
 
-                // Создаем Роутер для операций чтения
-                let mut router: Self::Router = <Self::Router as crate::abstraction::Router>::new(
-                    resource_inner.path().clone(),
-                    path_outer,
-                );
+                // Тома собираются в исходный файл, по умолчанию он записывается
+                // под именем без номера тома, а не на место первого тома
+                let path_outer = match (path_outer, base) {
+                    (Some(path), _) => path,
+                    (None, Some(base)) => <<R as crate::abstraction::Router>::Resource as UnifiedResourceIdentifierAbstraction>::Path::new(base, crate::abstraction::Operation::Create).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?,
+                    (None, None) => path_inner.clone(),
+                };
 
-                // 2. ЧТЕНИЕ ВСЕГО ФАЙЛА В БУФЕР (Операционный шаг 1)
-                router
-                    .read(&mut buf)
-                    .map_err(|e| crate::abstraction::error::Error::<Self>::ReaderError(e))?;
+                let router: R =
+                    <R as crate::abstraction::Router>::new(path_inner.clone(), Some(path_outer));
+
+                // Расшифровка на место исходного файла усекает его до записи,
+                // отображенные в память страницы нужно скопировать заранее
+                if let crate::abstraction::Data::Mapped(_) = buf {
+                    if router.in_place() {
+                        buf = buf.into_vec().into();
+                    }
+                }
 
                 // 3. ИЗВЛЕЧЕНИЕ ЗАГОЛОВКА (Scriber)
-                let scriber: Self::Scriber =
-                    <Self::Scriber as crate::abstraction::Header>::read_from_buffer(&buf)
-                        .map_err(|e| crate::abstraction::error::Error::<Self>::HeaderError(e))?;
+                let scriber: M = <M as crate::abstraction::Header>::read_from_buffer(&buf)
+                    .map_err(|e| crate::abstraction::error::Error::<Self>::HeaderError(e))?;
 
                 // 4. Генерация ключа
                 let salt = scriber.get_salt();
@@ -260,6 +539,11 @@ where
                     scriber: scriber,
                     key_deriver: derive_key,
                     cli: cli,
+                    section: scriber::section::Section::default(),
+                    armored,
+                    parity,
+                    repaired,
+                    auth_key: [0; auth::TAG_SIZE],
                     _marker_f: PhantomData::default(),
                     _marker_n: PhantomData::default(),
                     _marker_s: PhantomData::default(),
@@ -268,60 +552,1339 @@ where
         }
     }
 
-    fn run(&mut self) -> Result<(), crate::abstraction::error::Error<Self>> {
-        let size: usize = K::KEY_LENGTH;
+    /// Чтение зашифрованного файла, его заголовка и подготовка вывода ключа по паролю
+
+    fn open(
+        cli: interface::cli::Cli,
+        path: String,
+        password: String,
+    ) -> Result<Self, crate::abstraction::error::Error<Self>> {
+        // 1. Инициализация пути и роутера
+        let resource_path = <<R as crate::abstraction::Router>::Resource as UnifiedResourceIdentifierAbstraction>::Path::new(
+            path.to_string(),
+            crate::abstraction::Operation::Open
+        ).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
+
+        // Создаем Роутер для операций чтения, результат редактирования
+        // записывается на место исходного файла
+        let out = match &cli.command {
+            interface::cli::Command::Edit { .. } | interface::cli::Command::Repair { .. } => {
+                Some(resource_path.clone())
+            }
+            _ => None,
+        };
+
+        let mut router: R = <R as crate::abstraction::Router>::new(resource_path, out);
+
+        // 2. ЧТЕНИЕ ВСЕГО ФАЙЛА (Операционный шаг 1)
+        let buf = router
+            .read_to_end()
+            .map_err(|e| crate::abstraction::error::Error::<Self>::ReaderError(e))?;
+
+        let Layers {
+            buf,
+            armored,
+            parity,
+            repaired,
+            ..
+        } = Self::unwrap_layers(&cli.command, &path, buf)?;
+
+        // 3. ИЗВЛЕЧЕНИЕ ЗАГОЛОВКА (Scriber)
+        let scriber: M = <M as crate::abstraction::Header>::read_from_buffer(&buf)
+            .map_err(|e| crate::abstraction::error::Error::<Self>::HeaderError(e))?;
+
+        // 4. ГЕНЕРАЦИЯ КЛЮЧА
+        let salt = scriber.get_salt();
+
+        let nonce = scriber.get_nounce();
+
+        let secret_password: <<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Secret = <<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Secret::new(password.clone()); // Пароль как Secret
+        let params: <<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Params = <<<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf as crate::abstraction::KeyDeriver<String, ()>>::Params as core::default::Default>::default(); // Предполагаем Default для параметров
+        let derive_key = <<App<R, M, K, F, S, N> as crate::abstraction::Application>::Kdf>::new(
+            secret_password,
+            params,
+            salt,
+            nonce,
+        );
+
+        // 6. ФИНАЛЬНАЯ СБОРКА ПРИЛОЖЕНИЯ
+        Ok(App {
+            buffer: buf,             // Считанные данные
+            resource: router,        // Роутер
+            scriber: scriber,        // Прочитанный заголовок
+            key_deriver: derive_key, // KDF генератор ключей
+            cli: cli,
+            section: scriber::section::Section::default(),
+            armored,
+            parity,
+            repaired,
+            auth_key: [0; auth::TAG_SIZE],
+            _marker_f: PhantomData::default(),
+            _marker_n: PhantomData::default(),
+            _marker_s: PhantomData::default(),
+        })
+    }
+
+    /// Снятие внешних слоев прочитанного файла в порядке, обратном записи:
+    /// извлечение из контейнера, сборка томов, снятие текстовой обертки
+    /// и восстановление поврежденных фрагментов. Все это делается до разбора заголовка.
+    ///
+    /// Данные извлекаются из контейнера только командой decrypt с `--carrier`.
+    /// Тома читаются собранными, но записать результат обратно в набор томов
+    /// командами edit и repair нельзя.
+
+    fn unwrap_layers(
+        command: &interface::cli::Command,
+        path: &str,
+        mut buf: crate::abstraction::Data,
+    ) -> Result<Layers, crate::abstraction::error::Error<Self>> {
+        if let interface::cli::Command::Decrypt { carrier: true, .. } = command {
+            buf = stego::extract(&buf)
+                .map_err(crate::abstraction::error::Error::Application)?
+                .into();
+        }
+
+        let base = if volume::is_volume(&buf) {
+            if let interface::cli::Command::Edit { .. } | interface::cli::Command::Repair { .. } =
+                command
+            {
+                return Err(crate::abstraction::error::Error::Application(
+                    error::Error::SplitFile,
+                ));
+            }
+
+            let (base, joined) = Self::join_volumes(path, &buf)?;
+
+            buf = joined.into();
+
+            Some(base)
+        } else {
+            None
+        };
 
-        let mut buffer = vec![0u8; size];
+        let armored = armor::is_armored(&buf);
+
+        if armored {
+            buf = Self::unarmor(&buf)?.into();
+        }
+
+        let (buf, parity, repaired) = Self::recover(buf)?;
+
+        Ok(Layers {
+            buf,
+            armored,
+            parity,
+            repaired,
+            base,
+        })
+    }
+
+    /// Чтение ресурса целиком по пути
+
+    fn load(path: &str) -> Result<Vec<u8>, crate::abstraction::error::Error<Self>> {
+        let path = <<R as crate::abstraction::Router>::Resource as UnifiedResourceIdentifierAbstraction>::Path::new(
+            path.to_string(),
+            crate::abstraction::Operation::Open
+        ).map_err(|e| crate::abstraction::error::Error::<Self>::ResourcePathError(e))?;
+
+        <R as crate::abstraction::Router>::new(path, None)
+            .read_to_end()
+            .map(crate::abstraction::Data::into_vec)
+            .map_err(|e| crate::abstraction::error::Error::<Self>::ReaderError(e))
+    }
+
+    /// Сборка файла из томов по пути и содержимому любого из них.
+    ///
+    /// Возвращает исходное имя без номера тома и собранные данные. Каждый том
+    /// проверяется по контрольной сумме, номеру и идентификатору набора.
+
+    fn join_volumes(
+        path: &str,
+        first: &[u8],
+    ) -> Result<(String, Vec<u8>), crate::abstraction::error::Error<Self>> {
+        let broken = |name: &str| {
+            crate::abstraction::error::Error::Application(error::Error::BrokenVolume(
+                name.to_string(),
+            ))
+        };
+
+        let base = volume::base(path).ok_or_else(|| broken(path))?;
+
+        let (head, _, _) = volume::parse(first).ok_or_else(|| broken(path))?;
+
+        let mut data = Vec::new();
+
+        // Первый том с неверной контрольной суммой
+        let mut damaged = None;
+
+        for index in 1..=head.total {
+            let name = volume::name(base, index, head.total);
+
+            let bytes = if name == path {
+                first.to_vec()
+            } else {
+                Self::load(&name).map_err(|_| {
+                    crate::abstraction::error::Error::Application(error::Error::MissingVolume(
+                        name.clone(),
+                    ))
+                })?
+            };
+
+            let (volume, payload, intact) = volume::parse(&bytes).ok_or_else(|| broken(&name))?;
+
+            if volume.set != head.set || volume.total != head.total {
+                return Err(crate::abstraction::error::Error::Application(
+                    error::Error::ForeignVolume(name),
+                ));
+            }
+
+            if volume.index != index {
+                return Err(crate::abstraction::error::Error::Application(
+                    error::Error::MisplacedVolume(name, volume.index),
+                ));
+            }
+
+            if !intact && damaged.is_none() {
+                damaged = Some(name);
+            }
+
+            data.extend_from_slice(payload);
+        }
+
+        // Поврежденные тома допустимы, только если собранные данные защищены избыточностью
+        match damaged {
+            Some(name) if !parity::is_protected(&data) => Err(broken(&name)),
+            _ => Ok((base.to_string(), data)),
+        }
+    }
+
+    /// Запись результата томами `name.001`, `name.002`, ... на место пути записи.
+    ///
+    /// Каждый том записывается на путь результата и переименовывается, поэтому
+    /// тома появляются рядом с ним.
+
+    fn write_volumes(
+        &mut self,
+        data: &[u8],
+        size: usize,
+    ) -> Result<(), crate::abstraction::error::Error<Self>> {
+        let base = self.resource.out_path().map(|path| path.name()).ok_or(
+            crate::abstraction::error::Error::Application(error::Error::NameNotStored),
+        )?;
+
+        let volumes = volume::split(data, size);
+
+        let total = volumes.len() as u32;
+
+        for (index, data) in volumes.iter().enumerate() {
+            self.resource
+                .write(data)
+                .map_err(|e| crate::abstraction::error::Error::WriterError(e))?;
+
+            self.rename_output(&volume::name(&base, index as u32 + 1, total))?;
+        }
+
+        println!(
+            "Записано томов: {} ({} ... {})",
+            total,
+            volume::name(&base, 1, total),
+            volume::name(&base, total, total)
+        );
+
+        Ok(())
+    }
+
+    /// Снятие текстовой обертки.
+    ///
+    /// При неверной контрольной сумме данные принимаются, если они защищены
+    /// избыточностью: повреждение исправит `recover`.
+
+    fn unarmor(buf: &[u8]) -> Result<Vec<u8>, crate::abstraction::error::Error<Self>> {
+        match armor::decode(buf) {
+            Some((data, intact)) if intact || parity::is_protected(&data) => Ok(data),
+            _ => Err(crate::abstraction::error::Error::Application(
+                error::Error::BrokenArmor,
+            )),
+        }
+    }
+
+    /// Восстановление поврежденных фрагментов, если в файле есть данные избыточности.
+    ///
+    /// Возвращает данные, процент избыточности и число восстановленных фрагментов.
+
+    fn recover(
+        buf: crate::abstraction::Data,
+    ) -> Result<(crate::abstraction::Data, u8, usize), crate::abstraction::error::Error<Self>> {
+        if !parity::is_protected(&buf) {
+            return Ok((buf, 0, 0));
+        }
+
+        parity::decode(&buf)
+            .map(|decoded| (decoded.data.into(), decoded.parity, decoded.repaired))
+            .map_err(|chunk| {
+                crate::abstraction::error::Error::Application(match chunk {
+                    Some(index) => error::Error::UnrecoverableChunk(index),
+                    None => error::Error::BrokenParity,
+                })
+            })
+    }
+
+    /// Запечатанный файл в том виде, в каком он записывается: с данными
+    /// восстановления и в текстовой обертке, если они заданы
+
+    fn wrap(&self, sealed: Vec<u8>) -> Vec<u8> {
+        let protected = match self.parity {
+            0 => sealed,
+            parity => parity::encode(&sealed, parity),
+        };
+
+        if self.armored {
+            armor::encode(&protected)
+        } else {
+            protected
+        }
+    }
+
+    /// Шифр заголовка с ключом, выведенным из пароля.
+    ///
+    /// Из того же ключа выводится ключ кода аутентификации файла.
+
+    fn cipher(
+        &mut self,
+    ) -> Result<<CryptoFormat as EncryptionList>::Encryptions, crate::abstraction::error::Error<Self>>
+    {
+        let mut key = vec![0u8; K::KEY_LENGTH];
 
         self.key_deriver
-            .derive_key(&mut buffer)
+            .derive_key(&mut key)
             .map_err(|e| crate::abstraction::error::Error::KDFError(e))?;
 
-        let cipher = self
-            .scriber
+        self.auth_key = auth::derive(&key);
+
+        self.scriber
             .get_cipher()
-            .build(&mut buffer)
-            .map_err(|e| crate::abstraction::error::Error::EncryptionListError(e))?;
+            .build(&key)
+            .map_err(|e| crate::abstraction::error::Error::EncryptionListError(e))
+    }
+
+    /// Размер кода аутентификации в конце файла, в файлах первой версии его нет
+
+    fn tag_size(&self) -> usize {
+        if self.scriber.get_version() >= scriber::SECTION_VERSION {
+            auth::TAG_SIZE
+        } else {
+            0
+        }
+    }
+
+    /// Проверка кода аутентификации заголовка, раздела метаданных и данных.
+    ///
+    /// Пока код не проверен, расшифрованному содержимому файла доверять нельзя.
+
+    fn authenticate(&self) -> Result<(), crate::abstraction::error::Error<Self>> {
+        if self.tag_size() == 0 {
+            return Ok(());
+        }
+
+        let sealed = self
+            .buffer
+            .len()
+            .checked_sub(auth::TAG_SIZE)
+            .filter(|sealed| *sealed >= self.scriber.size())
+            .ok_or(crate::abstraction::error::Error::Application(
+                error::Error::Unauthenticated,
+            ))?;
+
+        if !auth::verify(
+            &self.auth_key,
+            &self.buffer[..sealed],
+            &self.buffer[sealed..],
+        ) {
+            return Err(crate::abstraction::error::Error::Application(
+                error::Error::Unauthenticated,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Проверка подлинности файла, расшифровка и разбор раздела метаданных заголовка
+
+    fn open_section<E>(&mut self, cipher: &E) -> Result<(), crate::abstraction::error::Error<Self>>
+    where
+        E: crate::abstraction::Encryption,
+    {
+        self.authenticate()?;
+
+        let mut section = self.scriber.get_section().to_vec();
+
+        cipher.decode(&mut section);
+
+        self.section = scriber::section::Section::from_bytes(&section).ok_or(
+            crate::abstraction::error::Error::Application(error::Error::BrokenSection),
+        )?;
+
+        Ok(())
+    }
+
+    /// Алгоритм сжатия из раздела метаданных, файлы без записи не сжаты
+
+    fn compression(&self) -> Result<CompressionFormat, crate::abstraction::error::Error<Self>> {
+        match self.section.get(scriber::section::tag::COMPRESSION) {
+            Some([byte]) => CompressionFormat::from_byte(*byte).map_err(|e| {
+                crate::abstraction::error::Error::Application(error::Error::Compression(e))
+            }),
+            Some(_) => Err(crate::abstraction::error::Error::Application(
+                error::Error::BrokenSection,
+            )),
+            None => Ok(CompressionFormat::None),
+        }
+    }
+
+    /// Схема дополнения из раздела метаданных, файлы без записи не дополнены
+
+    fn padding(&self) -> Result<PaddingScheme, crate::abstraction::error::Error<Self>> {
+        match self.section.get(scriber::section::tag::PADDING_SCHEME) {
+            Some(bytes) => PaddingScheme::from_bytes(bytes).ok_or(
+                crate::abstraction::error::Error::Application(error::Error::BrokenSection),
+            ),
+            None => Ok(PaddingScheme::None),
+        }
+    }
+
+    /// Диапазон данных в `buffer` без заголовка, дополнения, длина которого
+    /// хранится в разделе метаданных, и кода аутентификации
+
+    fn payload(&self) -> Result<std::ops::Range<usize>, crate::abstraction::error::Error<Self>> {
+        let start = self.scriber.size();
+
+        let len = self.buffer.len() - start - self.tag_size();
+
+        let pad_len = match self.section.get(scriber::section::tag::PADDING) {
+            Some(bytes) => bytes
+                .try_into()
+                .map(|bytes| u64::from_le_bytes(bytes) as usize)
+                .ok()
+                .filter(|pad_len| *pad_len <= len)
+                .ok_or(crate::abstraction::error::Error::Application(
+                    error::Error::BrokenSection,
+                ))?,
+            None => 0,
+        };
+
+        Ok(start..start + len - pad_len)
+    }
+
+    fn decompress(&self, buf: &[u8]) -> Result<Vec<u8>, crate::abstraction::error::Error<Self>> {
+        self.compression()?.decompress(buf).map_err(|e| {
+            crate::abstraction::error::Error::Application(error::Error::Compression(e))
+        })
+    }
+
+    /// Сжатие `buffer`, расчет дополнения и шифрование раздела метаданных.
+    ///
+    /// Возвращает размер файла и длину дополнения. Сами данные шифруются
+    /// и аутентифицируются при заполнении файла в `fill_sealed`.
+
+    fn pack<E>(
+        &mut self,
+        cipher: &E,
+        pad: &PaddingScheme,
+    ) -> Result<(usize, usize), crate::abstraction::error::Error<Self>>
+    where
+        E: crate::abstraction::Encryption + Sync,
+    {
+        let compressed = self.compression()?.compress(&self.buffer).map_err(|e| {
+            crate::abstraction::error::Error::Application(error::Error::Compression(e))
+        })?;
+
+        // Несжимаемые данные сохраняются как есть
+        if compressed.len() < self.buffer.len() {
+            self.buffer = compressed.into();
+        } else {
+            self.section.insert(
+                scriber::section::tag::COMPRESSION,
+                vec![CompressionFormat::None.to_byte()],
+            );
+        }
+
+        // Дополнение выравнивает весь файл вместе с заголовком,
+        // поэтому сначала учитывается запись о его длине
+        let pad_len = if *pad != PaddingScheme::None {
+            self.section
+                .insert(scriber::section::tag::PADDING_SCHEME, pad.to_bytes());
+
+            self.section
+                .insert(scriber::section::tag::PADDING, 0u64.to_le_bytes().to_vec());
+
+            self.scriber.set_section(self.section.to_bytes());
+
+            let len = self.buffer.len() + self.scriber.size() + auth::TAG_SIZE;
+
+            let pad_len = pad.padded_len(len) - len;
+
+            self.section.insert(
+                scriber::section::tag::PADDING,
+                (pad_len as u64).to_le_bytes().to_vec(),
+            );
+
+            pad_len
+        } else {
+            0
+        };
+
+        let mut section = self.section.to_bytes();
+
+        cipher.encode(&mut section);
+
+        self.scriber.set_section(section);
+
+        Ok((
+            self.scriber.size() + self.buffer.len() + pad_len + auth::TAG_SIZE,
+            pad_len,
+        ))
+    }
+
+    /// Заголовок файла вместе с зашифрованным разделом метаданных
+
+    fn header(&mut self) -> Vec<u8> {
+        let mut header = vec![0u8; self.scriber.size()];
+
+        self.scriber.write_to_buffer(&mut [], &mut header);
+
+        header
+    }
+
+    /// Заполняет `out` заголовком, данными и нулевым дополнением и шифрует
+    /// данные на месте, в том числе прямо в отображенных страницах файла.
+    /// Последние байты `out` занимает код аутентификации всего остального.
+    ///
+    /// Данные шифруются со смещения `offset` в потоке шифра, следом за разделом
+    /// метаданных, чтобы не использовать повторно его часть потока.
+
+    fn fill_sealed<E>(
+        header: &[u8],
+        data: &[u8],
+        cipher: &E,
+        offset: usize,
+        jobs: usize,
+        auth_key: &[u8],
+        out: &mut [u8],
+    ) where
+        E: crate::abstraction::Encryption + Sync,
+    {
+        let (sealed, tag) = out.split_at_mut(out.len() - auth::TAG_SIZE);
+
+        let (head, body) = sealed.split_at_mut(header.len());
+
+        head.copy_from_slice(header);
 
-        let mut res_buf = match &self.cli.command {
-            interface::cli::Command::Prepare { .. } => {
-                let mut res_buf = vec![0u8; self.buffer.len() + 42]; // 42 так как подпись
-                cipher.encode(&mut self.buffer);
+        body[..data.len()].copy_from_slice(data);
 
-                self.scriber.write_to_buffer(&mut self.buffer, &mut res_buf);
+        body[data.len()..].fill(0);
 
-                res_buf
+        pool::encode(cipher, body, offset, jobs);
+
+        tag.copy_from_slice(&auth::tag(auth_key, sealed));
+    }
+
+    /// Сжатие, дополнение и шифрование `buffer` вместе с разделом метаданных.
+    ///
+    /// Возвращает содержимое файла: заголовок и зашифрованные данные.
+
+    fn seal<E>(
+        &mut self,
+        cipher: &E,
+        pad: &PaddingScheme,
+    ) -> Result<Vec<u8>, crate::abstraction::error::Error<Self>>
+    where
+        E: crate::abstraction::Encryption + Sync,
+    {
+        let (len, _) = self.pack(cipher, pad)?;
+
+        let header = self.header();
+
+        let mut res_buf = vec![0u8; len];
+
+        Self::fill_sealed(
+            &header,
+            &self.buffer,
+            cipher,
+            self.keystream_offset(),
+            self.cli.jobs(),
+            &self.auth_key,
+            &mut res_buf,
+        );
+
+        Ok(res_buf)
+    }
+
+    /// Запечатывает `buffer` и записывает результат.
+    ///
+    /// Без текстовой обертки, избыточности, контейнера и томов файл собирается
+    /// и шифруется прямо на месте записи, иначе возвращается содержимое для записи.
+
+    fn write_sealed<E>(
+        &mut self,
+        cipher: &E,
+        pad: &PaddingScheme,
+    ) -> Result<Option<Vec<u8>>, crate::abstraction::error::Error<Self>>
+    where
+        E: crate::abstraction::Encryption + Sync,
+    {
+        let (carrier, split) = match &self.cli.command {
+            interface::cli::Command::Prepare { carrier, split, .. } => (carrier.clone(), *split),
+            _ => (None, None),
+        };
+
+        if self.parity > 0 || self.armored || carrier.is_some() || split.is_some() {
+            let sealed = self.seal(cipher, pad)?;
+
+            let wrapped = self.wrap(sealed);
+
+            return match carrier {
+                Some(carrier) => stego::embed(&Self::load(&carrier)?, &wrapped)
+                    .map(Some)
+                    .map_err(crate::abstraction::error::Error::Application),
+                None => Ok(Some(wrapped)),
+            };
+        }
+
+        let (len, _) = self.pack(cipher, pad)?;
+
+        let header = self.header();
+
+        let (data, offset, jobs, auth_key) = (
+            &self.buffer,
+            self.keystream_offset(),
+            self.cli.jobs(),
+            &self.auth_key,
+        );
+
+        self.resource
+            .write_with(len, &mut |out| {
+                Self::fill_sealed(&header, data, cipher, offset, jobs, auth_key, out)
+            })
+            .map_err(|e| crate::abstraction::error::Error::WriterError(e))?;
+
+        Ok(None)
+    }
+
+    /// Смещение данных в потоке шифра: поток начинается с раздела метаданных,
+    /// данные шифруются его продолжением
+
+    fn keystream_offset(&self) -> usize {
+        self.scriber.get_section().len()
+    }
+
+    /// Расшифровка раздела метаданных и данных из `buffer` без дополнения и сжатия
+
+    fn unseal<E>(&mut self, cipher: &E) -> Result<Vec<u8>, crate::abstraction::error::Error<Self>>
+    where
+        E: crate::abstraction::Encryption + Sync,
+    {
+        self.open_section(cipher)?;
+
+        let mut res_buf = self.buffer[self.payload()?].to_vec();
+
+        pool::decode(
+            cipher,
+            &mut res_buf,
+            self.keystream_offset(),
+            self.cli.jobs(),
+        );
+
+        self.decompress(&res_buf)
+    }
+
+    /// Сведения о заголовке, исходное имя выводится только при верном пароле
+
+    fn print_info<E>(&mut self, cipher: &E)
+    where
+        E: crate::abstraction::Encryption,
+    {
+        println!("Версия формата: {}", self.scriber.get_version());
+
+        println!("Шифрование: {}", self.scriber.get_cipher());
+
+        if self.armored {
+            println!("Текстовая обертка: Base64");
+        }
+
+        if self.parity > 0 {
+            println!("Избыточность: {}%", self.parity);
+        }
+
+        if self.repaired > 0 {
+            println!("Восстановлено поврежденных фрагментов: {}", self.repaired);
+        }
+
+        println!("Формат данных: {:?}", self.scriber.get_format());
+
+        println!(
+            "Размер данных: {} байт",
+            (self.buffer.len() - self.scriber.size()).saturating_sub(self.tag_size())
+        );
+
+        // Раздел, расшифрованный верным паролем, разбирается всегда, в том числе
+        // пустой раздел файлов первой версии, где имя не хранится
+        match self.open_section(cipher) {
+            Ok(()) => match self.stored_name() {
+                Ok(name) => println!("Исходное имя: {}", name),
+                Err(_) => println!("Исходное имя: не сохранено в заголовке"),
+            },
+            Err(_) => println!("Исходное имя: скрыто, требуется верный пароль"),
+        }
+    }
+
+    /// Переименование записанного результата в пределах его расположения
+
+    fn rename_output(
+        &self,
+        name: &str,
+    ) -> Result<
+        <<R as crate::abstraction::Router>::Resource as UnifiedResourceIdentifierAbstraction>::Path,
+        crate::abstraction::error::Error<Self>,
+    > {
+        let out = self
+            .resource
+            .out_path()
+            .ok_or(crate::abstraction::error::Error::Application(
+                error::Error::NameNotStored,
+            ))?;
+
+        out.rename(name)
+            .map_err(|e| crate::abstraction::error::Error::ResourcePathError(e))
+    }
+
+    /// Исходное имя из раздела метаданных
+
+    fn stored_name(&self) -> Result<String, crate::abstraction::error::Error<Self>> {
+        self.section
+            .get(scriber::section::tag::NAME)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .ok_or(crate::abstraction::error::Error::Application(
+                error::Error::NameNotStored,
+            ))
+    }
+
+    /// Восстановление метаданных исходного файла на пути записи
+
+    fn restore_metadata(&self) -> Result<(), crate::abstraction::error::Error<Self>> {
+        let (Some(metadata), Some(out)) = (
+            self.section.get(scriber::section::tag::METADATA),
+            self.resource.out_path(),
+        ) else {
+            return Ok(());
+        };
+
+        out.set_metadata(metadata)
+            .map_err(|e| crate::abstraction::error::Error::ResourcePathError(e))
+    }
+}
+
+impl<R, M, K, F, S, N> crate::abstraction::Application for App<R, M, K, F, S, N>
+where
+    F: crate::abstraction::ResourceTypeList,
+    R: crate::abstraction::Router<
+            Resource: crate::abstraction::UnifiedResourceIdentifierAbstraction<Type = F>,
+        >,
+    M: crate::abstraction::Header<Format = F, Nonce = N, Salt = S, Cipher = CryptoFormat>,
+    S: crate::abstraction::SaltProvider,
+    N: crate::abstraction::NonceProvider,
+    K: crate::abstraction::KeyDeriver<String, (), Salt = S, Nonce = N>,
+{
+    type Error = error::Error;
+
+    type Router = R;
+
+    type Scriber = M;
+
+    type Kdf = K;
+
+    fn run(&mut self) -> Result<(), crate::abstraction::error::Error<Self>> {
+        let cipher = self.cipher()?;
+
+        // `None` - результат уже записан на месте
+        let res_buf = match &self.cli.command {
+            interface::cli::Command::Prepare { pad, .. } => {
+                let pad = *pad;
+
+                self.write_sealed(&cipher, &pad)?
+            }
+            interface::cli::Command::Hide { .. } | interface::cli::Command::Reveal { .. } => {
+                unreachable!("hide и reveal выполняются как prepare и decrypt")
+            }
+            interface::cli::Command::Read { view, .. } => {
+                let (options, json, page) = (view.options(), view.json, view.page);
+
+                let res_buf = self.unseal(&cipher)?;
+
+                let blocks = self.scriber.get_format().render(&res_buf, &options);
+
+                let lines = if json {
+                    vec![interface::output::json(&blocks)]
+                } else {
+                    interface::output::text(&blocks)
+                };
+
+                interface::output::print(&lines, page).map_err(|e| {
+                    crate::abstraction::error::Error::Application(error::Error::Output(e))
+                })?;
+
+                return Ok(());
+            }
+            interface::cli::Command::Info { .. } => {
+                self.print_info(&cipher);
+
+                return Ok(());
+            }
+            interface::cli::Command::EncryptText { .. }
+            | interface::cli::Command::DecryptText { .. } => {
+                return Err(crate::abstraction::error::Error::Application(
+                    error::Error::NotFileCommand,
+                ));
             }
-            interface::cli::Command::Read { .. } => {
-                let mut res_buf = vec![0u8; self.buffer.len() - 42];
+            interface::cli::Command::Diff {
+                a,
+                b,
+                password,
+                password_b,
+                context,
+                ..
+            } => {
+                let (names, context) = ((a.clone(), b.clone()), *context);
+
+                let password_b = password_b.clone().unwrap_or_else(|| password.clone());
+
+                // Второй файл открывается отдельным приложением со своим паролем
+                let mut other = Self::open(self.cli.clone(), names.1.clone(), password_b)?;
+
+                let old = (self.scriber.get_format(), self.unseal(&cipher)?);
+
+                let other_cipher = other.cipher()?;
+
+                let new = (other.scriber.get_format(), other.unseal(&other_cipher)?);
+
+                let renderers = F::renderers();
+
+                let names = (names.0.as_str(), names.1.as_str());
+
+                // Текст сравнивается, если его можно извлечь из обоих файлов
+                let out = if renderers.contains(&old.0) && renderers.contains(&new.0) {
+                    let content = |(format, data): &(F, Vec<u8>)| {
+                        interface::output::content(&renderers.render(
+                            format,
+                            data,
+                            &crate::abstraction::ViewOptions::default(),
+                        ))
+                    };
+
+                    interface::diff::text(names, &content(&old), &content(&new), context)
+                } else {
+                    interface::diff::bytes(names, &old.1, &new.1)
+                };
+
+                interface::output::print(&out, false).map_err(|e| {
+                    crate::abstraction::error::Error::Application(error::Error::Output(e))
+                })?;
+
+                // Различие, как и у diff, сообщается кодом возврата, пустой вывод - файлы одинаковы
+                if !out.is_empty() {
+                    return Err(crate::abstraction::error::Error::Application(
+                        error::Error::Differ,
+                    ));
+                }
+
+                return Ok(());
+            }
+            interface::cli::Command::Grep {
+                pattern,
+                paths,
+                fixed_strings,
+                ignore_case,
+                count,
+                line_number,
+                ..
+            } => {
+                let pattern = if *fixed_strings {
+                    regex::escape(pattern)
+                } else {
+                    pattern.clone()
+                };
+
+                let regex = regex::RegexBuilder::new(&pattern)
+                    .case_insensitive(*ignore_case)
+                    .build()
+                    .map_err(|e| {
+                        crate::abstraction::error::Error::Application(error::Error::Pattern(e))
+                    })?;
+
+                let (name, count, line_number) = (paths.join(" "), *count, *line_number);
+
+                let data = self.unseal(&cipher)?;
+
+                let format = self.scriber.get_format();
+
+                let renderers = F::renderers();
+
+                // Двоичные данные без своего обработчика показываются только дампом,
+                // искать в нем текст бессмысленно
+                if !renderers.contains(&format) {
+                    eprintln!("{}: формат {:?} без текста, пропущен", name, format);
+
+                    return Err(crate::abstraction::error::Error::Application(
+                        error::Error::NoMatch,
+                    ));
+                }
+
+                let blocks =
+                    renderers.render(&format, &data, &crate::abstraction::ViewOptions::default());
+
+                for block in &blocks {
+                    if let crate::abstraction::Block::Error(e) = block {
+                        eprintln!("{}: {}", name, e);
+                    }
+                }
+
+                let lines = interface::output::content(&blocks);
 
-                self.scriber
-                    .remove_from_buffer(&mut self.buffer, &mut res_buf);
+                let found: Vec<(usize, &String)> = lines
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, line)| regex.is_match(line))
+                    .collect();
 
-                cipher.decode(&mut res_buf);
+                let matched = !found.is_empty();
 
-                self.scriber.get_format().print_function(&mut res_buf);
+                // Строки одного файла печатаются разом, чтобы не перемешаться
+                // с выводом других файлов, которые ищутся параллельно
+                let out: Vec<String> = if count {
+                    vec![format!("{}:{}", name, found.len())]
+                } else if line_number {
+                    found
+                        .into_iter()
+                        .map(|(index, line)| format!("{}:{}:{}", name, index + 1, line))
+                        .collect()
+                } else {
+                    found
+                        .into_iter()
+                        .map(|(_, line)| format!("{}:{}", name, line))
+                        .collect()
+                };
+
+                interface::output::print(&out, false).map_err(|e| {
+                    crate::abstraction::error::Error::Application(error::Error::Output(e))
+                })?;
+
+                // Отсутствие совпадений различается по коду возврата, а не сообщению
+                if !matched {
+                    return Err(crate::abstraction::error::Error::Application(
+                        error::Error::NoMatch,
+                    ));
+                }
 
                 return Ok(());
             }
-            interface::cli::Command::Decrypt { .. } => {
-                println!("{:?}", self.scriber.to_byte());
+            interface::cli::Command::Decrypt { restore_name, .. } => {
+                let restore_name = *restore_name;
+
+                if self.repaired > 0 {
+                    eprintln!("Восстановлено поврежденных фрагментов: {}", self.repaired);
+                }
+
+                self.open_section(&cipher)?;
+
+                // Занятое исходное имя обнаруживается до записи, иначе
+                // расшифрованные данные остались бы на диске под временным именем
+                if restore_name {
+                    let name = self.stored_name()?;
+
+                    if let Some(out) = self.resource.out_path() {
+                        out.check_rename(&name)
+                            .map_err(|e| crate::abstraction::error::Error::ResourcePathError(e))?;
+                    }
+                }
+
+                match self.compression()? {
+                    // Несжатые данные расшифровываются прямо на месте записи
+                    CompressionFormat::None => {
+                        let payload = self.payload()?;
+
+                        let (data, offset, jobs) = (
+                            &self.buffer[payload.clone()],
+                            self.keystream_offset(),
+                            self.cli.jobs(),
+                        );
+
+                        self.resource
+                            .write_with(payload.len(), &mut |out| {
+                                out.copy_from_slice(data);
+
+                                pool::decode(&cipher, out, offset, jobs);
+                            })
+                            .map_err(|e| crate::abstraction::error::Error::WriterError(e))?;
+
+                        None
+                    }
+                    _ => Some(self.unseal(&cipher)?),
+                }
+            }
+            interface::cli::Command::Repair { .. } => {
+                if self.parity == 0 {
+                    return Err(crate::abstraction::error::Error::Application(
+                        error::Error::NotProtected,
+                    ));
+                }
 
-                let mut res_buf = vec![0u8; self.buffer.len() - 42];
+                if self.repaired == 0 {
+                    println!("Повреждений не найдено");
 
-                self.scriber
-                    .remove_from_buffer(&mut self.buffer, &mut res_buf);
+                    return Ok(());
+                }
 
-                cipher.decode(&mut res_buf);
+                // Данные восстановления вычисляются заново, как при шифровании
+                let sealed = std::mem::take(&mut self.buffer).into_vec();
 
-                res_buf
+                Some(self.wrap(sealed))
+            }
+            interface::cli::Command::Edit { password, pad, .. } => {
+                let (password, pad) = (password.clone(), *pad);
+
+                let data = self.unseal(&cipher)?;
+
+                let name = self
+                    .section
+                    .get(scriber::section::tag::NAME)
+                    .map(|name| String::from_utf8_lossy(name).into_owned())
+                    .unwrap_or_else(|| {
+                        self.resource
+                            .out_path()
+                            .map_or_else(String::new, |path| path.name())
+                    });
+
+                let Some(edited) = interface::editor::edit(&name, &data).map_err(|e| {
+                    crate::abstraction::error::Error::Application(error::Error::Editor(e))
+                })?
+                else {
+                    println!("Файл не изменен");
+
+                    return Ok(());
+                };
+
+                // Nonce выводится из пароля и соли, поэтому новый nonce требует новой соли
+                let salt = <K as crate::abstraction::KeyDeriver<String, ()>>::Salt::generate()
+                    .map_err(|e| crate::abstraction::error::Error::SaltError(e))?;
+
+                let nonce = <K as crate::abstraction::KeyDeriver<String, ()>>::Nonce::generate(
+                    &password,
+                    salt.as_bytes(),
+                )
+                .map_err(|e| crate::abstraction::error::Error::NonceError(e))?;
+
+                self.key_deriver = K::new(
+                    <K as crate::abstraction::KeyDeriver<String, ()>>::Secret::new(password),
+                    Default::default(),
+                    salt.clone(),
+                    nonce.clone(),
+                );
+
+                self.scriber = <M as crate::abstraction::Header>::new(
+                    self.scriber.get_format(),
+                    self.scriber.get_cipher(),
+                    salt,
+                    nonce,
+                );
+
+                let cipher = self.cipher()?;
+
+                let pad = match pad {
+                    Some(pad) => pad,
+                    None => self.padding()?,
+                };
+
+                self.section.remove(scriber::section::tag::PADDING_SCHEME);
+
+                self.section.remove(scriber::section::tag::PADDING);
+
+                self.buffer = edited.into();
+
+                self.write_sealed(&cipher, &pad)?
             }
         };
 
-        self.resource
-            .write(&mut res_buf)
-            .map_err(|e| crate::abstraction::error::Error::WriterError(e))?;
+        if let Some(res_buf) = res_buf {
+            if let interface::cli::Command::Prepare {
+                split: Some(size), ..
+            } = &self.cli.command
+            {
+                let size = *size;
+
+                return self.write_volumes(&res_buf, size);
+            }
+
+            self.resource
+                .write(&res_buf)
+                .map_err(|e| crate::abstraction::error::Error::WriterError(e))?;
+        }
+
+        match &self.cli.command {
+            interface::cli::Command::Prepare {
+                obfuscate_name: true,
+                ..
+            } => {
+                let name = rand::random::<[u8; 16]>()
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<String>();
+
+                let path = self.rename_output(&name)?;
+
+                println!("Файл сохранен под именем: {}", path.name());
+            }
+            interface::cli::Command::Prepare {
+                carrier: Some(_), ..
+            } => {
+                if let Some(out) = self.resource.out_path() {
+                    println!("Файл спрятан в {}", out.name());
+                }
+            }
+            interface::cli::Command::Decrypt {
+                no_preserve,
+                restore_name,
+                ..
+            } => {
+                if !no_preserve {
+                    self.restore_metadata()?;
+                }
+
+                if *restore_name {
+                    let name = self.stored_name()?;
+
+                    let path = self.rename_output(&name)?;
+
+                    println!("Файл восстановлен под именем: {}", path.name());
+                }
+            }
+            interface::cli::Command::Repair { .. } => {
+                println!("Восстановлено поврежденных фрагментов: {}", self.repaired);
+            }
+            _ => {}
+        }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::realisation::{derive_key::standard, object::file::resource_type::ResourceType};
+    use clap::Parser;
+
+    type MemoryApp = App<
+        router::Router<memory::MemoryResource>,
+        scriber::Scriber<
+            ResourceType,
+            CryptoFormat,
+            standard::salt::StandardSalt,
+            standard::nonce::StandardNonce,
+        >,
+        standard::StandardKdf<32>,
+        ResourceType,
+        standard::salt::StandardSalt,
+        standard::nonce::StandardNonce,
+    >;
+
+    type FileApp = App<
+        router::Router<crate::realisation::object::file::FileResourceIdentifier>,
+        scriber::Scriber<
+            ResourceType,
+            CryptoFormat,
+            standard::salt::StandardSalt,
+            standard::nonce::StandardNonce,
+        >,
+        standard::StandardKdf<32>,
+        ResourceType,
+        standard::salt::StandardSalt,
+        standard::nonce::StandardNonce,
+    >;
+
+    /// Запуск команды над ресурсами в памяти, как из командной строки
+
+    fn run(args: &[&str]) -> Result<(), crate::abstraction::error::Error<MemoryApp>> {
+        let cli = interface::cli::Cli::try_parse_from(
+            std::iter::once("De-En_coder").chain(args.iter().copied()),
+        )
+        .expect("аргументы теста разбираются");
+
+        MemoryApp::with_cli(cli)?.run()
+    }
+
+    /// Шифрование `data` из `mem://<name>.in` в `mem://<name>.enc`
+
+    fn prepare(name: &str, data: &[u8], args: &[&str]) -> Vec<u8> {
+        memory::path::put(&format!("{}.in", name), data.to_vec());
+
+        let (input, output) = (format!("mem://{}.in", name), format!("mem://{}.enc", name));
+
+        let mut command = vec![
+            "prepare",
+            &input,
+            "--path-outer",
+            &output,
+            "--password",
+            "secret",
+        ];
+
+        command.extend_from_slice(args);
+
+        run(&command).expect("шифрование в памяти");
+
+        memory::path::take(&format!("{}.in", name));
+
+        memory::path::take(&format!("{}.enc", name)).expect("результат шифрования записан")
+    }
+
+    /// Расшифровка `encrypted` из `mem://<name>.enc` в `mem://<name>.out`
+
+    fn decrypt(
+        name: &str,
+        encrypted: Vec<u8>,
+        password: &str,
+    ) -> Result<Vec<u8>, crate::abstraction::error::Error<MemoryApp>> {
+        memory::path::put(&format!("{}.enc", name), encrypted);
+
+        let (input, output) = (format!("mem://{}.enc", name), format!("mem://{}.out", name));
+
+        let result = run(&[
+            "decrypt",
+            &input,
+            "--path-outer",
+            &output,
+            "--password",
+            password,
+        ]);
+
+        memory::path::take(&format!("{}.enc", name));
+
+        let output = memory::path::take(&format!("{}.out", name));
+
+        result.map(|_| output.unwrap_or_default())
+    }
+
+    fn sample() -> Vec<u8> {
+        (0..20_000u32)
+            .flat_map(|i| (i * 7919).to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = sample();
+
+        let encrypted = prepare("round_trip", &data, &[]);
+
+        assert!(!encrypted.windows(64).any(|window| window == &data[..64]));
+
+        assert_eq!(decrypt("round_trip", encrypted, "secret").unwrap(), data);
+    }
+
+    #[test]
+    fn wrong_password_is_rejected() {
+        let encrypted = prepare("wrong_password", &sample(), &[]);
+
+        assert!(matches!(
+            decrypt("wrong_password", encrypted, "other"),
+            Err(crate::abstraction::error::Error::Application(
+                error::Error::Unauthenticated
+            ))
+        ));
+    }
+
+    #[test]
+    fn in_place_round_trip_of_mapped_file() {
+        // Файл больше порога отображения в память, результат пишется поверх него
+        let data: Vec<u8> = (0..3u32 << 20)
+            .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+            .collect();
+
+        let path = std::env::temp_dir().join(format!("de-en-in-place-{}", std::process::id()));
+
+        std::fs::write(&path, &data).unwrap();
+
+        let path_name = path.to_string_lossy().into_owned();
+
+        for args in [
+            vec![
+                "prepare",
+                &path_name,
+                "--password",
+                "secret",
+                "--compress",
+                "none",
+            ],
+            vec!["decrypt", &path_name, "--password", "secret"],
+        ] {
+            let cli = interface::cli::Cli::try_parse_from(
+                std::iter::once("De-En_coder").chain(args.iter().copied()),
+            )
+            .expect("аргументы теста разбираются");
+
+            FileApp::with_cli(cli)
+                .and_then(|mut app| app.run())
+                .unwrap();
+        }
+
+        let decrypted = std::fs::read(&path).unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(decrypted == data);
+    }
+
+    #[test]
+    fn round_trip_uncompressed() {
+        let data = sample();
+
+        let encrypted = prepare("round_trip_uncompressed", &data, &["--compress", "none"]);
+
+        assert_eq!(
+            decrypt("round_trip_uncompressed", encrypted, "secret").unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn padding_aligns_file_size() {
+        let data = sample();
+
+        let encrypted = prepare("padding", &data, &["--pad", "block:4096"]);
+
+        assert_eq!(encrypted.len() % 4096, 0);
+
+        assert_eq!(decrypt("padding", encrypted, "secret").unwrap(), data);
+    }
+
+    #[test]
+    fn armor_round_trip() {
+        let data = sample();
+
+        let encrypted = prepare("armor", &data, &["--armor"]);
+
+        assert!(armor::is_armored(&encrypted));
+
+        assert_eq!(decrypt("armor", encrypted, "secret").unwrap(), data);
+    }
+
+    #[test]
+    fn parity_repairs_damage() {
+        let data = sample();
+
+        let mut encrypted = prepare("parity", &data, &["--parity", "10%", "--compress", "none"]);
+
+        encrypted[5000..5008].fill(0xFF);
+
+        assert_eq!(decrypt("parity", encrypted, "secret").unwrap(), data);
+    }
+}
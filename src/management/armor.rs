@@ -0,0 +1,116 @@
+use base64::Engine;
+
+/// Первая строка текстовой обертки
+
+const BEGIN: &str = "-----BEGIN DE-EN_CODER MESSAGE-----";
+
+/// Последняя строка текстовой обертки
+
+const END: &str = "-----END DE-EN_CODER MESSAGE-----";
+
+/// Длина строки Base64, как в OpenPGP
+
+const LINE_WIDTH: usize = 64;
+
+/// Оборачивает файл в текст: рамка `BEGIN`/`END`, Base64 по строкам и строка
+/// контрольной суммы `=XXXX` (CRC-24 в Base64, как в OpenPGP)
+
+pub fn encode(bytes: &[u8]) -> Vec<u8> {
+    let engine = base64::engine::general_purpose::STANDARD;
+
+    let body = engine.encode(bytes);
+
+    let mut text = String::with_capacity(body.len() + body.len() / LINE_WIDTH + 128);
+
+    text.push_str(BEGIN);
+
+    text.push_str("\n\n");
+
+    for line in body.as_bytes().chunks(LINE_WIDTH) {
+        text.push_str(&String::from_utf8_lossy(line));
+
+        text.push('\n');
+    }
+
+    text.push('=');
+
+    text.push_str(&engine.encode(&crc24(bytes).to_be_bytes()[1..]));
+
+    text.push('\n');
+
+    text.push_str(END);
+
+    text.push('\n');
+
+    text.into_bytes()
+}
+
+/// Начинаются ли данные с текстовой обертки, пробелы перед ней допускаются
+
+pub fn is_armored(bytes: &[u8]) -> bool {
+    bytes.trim_ascii_start().starts_with(BEGIN.as_bytes())
+}
+
+/// Снимает текстовую обертку и проверяет контрольную сумму.
+///
+/// Строки могут быть с отступами и концами `\r\n`, например после вставки в YAML или письмо.
+/// Второе значение - совпала ли контрольная сумма.
+
+pub fn decode(bytes: &[u8]) -> Option<(Vec<u8>, bool)> {
+    let text = std::str::from_utf8(bytes).ok()?;
+
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty());
+
+    if lines.next()? != BEGIN {
+        return None;
+    }
+
+    let mut body = String::new();
+
+    let mut checksum = None;
+
+    for line in lines.by_ref() {
+        match line {
+            END => break,
+            _ if line.starts_with('=') => checksum = Some(&line[1..]),
+            _ => body.push_str(line),
+        }
+    }
+
+    let engine = base64::engine::general_purpose::STANDARD;
+
+    let data = engine.decode(body).ok()?;
+
+    let checksum = engine.decode(checksum?).ok()?;
+
+    let intact = checksum == crc24(&data).to_be_bytes()[1..];
+
+    Some((data, intact))
+}
+
+/// CRC-24 из RFC 4880, раздел 6.1
+
+fn crc24(bytes: &[u8]) -> u32 {
+    const INIT: u32 = 0xB704CE;
+
+    const POLY: u32 = 0x1864CFB;
+
+    let mut crc = INIT;
+
+    for byte in bytes {
+        crc ^= (*byte as u32) << 16;
+
+        for _ in 0..8 {
+            crc <<= 1;
+
+            if crc & 0x1000000 != 0 {
+                crc ^= POLY;
+            }
+        }
+    }
+
+    crc & 0xFFFFFF
+}
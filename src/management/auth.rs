@@ -0,0 +1,71 @@
+use sha2::Digest;
+
+/// Размер кода аутентификации HMAC-SHA256 и ключа для него
+
+pub const TAG_SIZE: usize = 32;
+
+/// Размер блока SHA-256, ключ HMAC дополняется до него
+
+const BLOCK_SIZE: usize = 64;
+
+/// Ключ аутентификации выводится из ключа шифра, чтобы не использовать
+/// один и тот же ключ для двух разных задач
+
+pub fn derive(key: &[u8]) -> [u8; TAG_SIZE] {
+    tag(key, b"DE-EN authentication key")
+}
+
+/// Код аутентификации HMAC-SHA256 (RFC 2104)
+
+pub fn tag(key: &[u8], data: &[u8]) -> [u8; TAG_SIZE] {
+    let mut block = [0u8; BLOCK_SIZE];
+
+    if key.len() > BLOCK_SIZE {
+        block[..TAG_SIZE].copy_from_slice(&sha2::Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let inner = sha2::Sha256::new()
+        .chain_update(block.map(|byte| byte ^ 0x36))
+        .chain_update(data)
+        .finalize();
+
+    sha2::Sha256::new()
+        .chain_update(block.map(|byte| byte ^ 0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// Проверка кода аутентификации. Сравнение не прерывается на первом
+/// расхождении, чтобы время проверки не подсказывало верные байты
+
+pub fn verify(key: &[u8], data: &[u8], expected: &[u8]) -> bool {
+    expected.len() == TAG_SIZE
+        && tag(key, data)
+            .iter()
+            .zip(expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_rfc_4231() {
+        let expected = [
+            0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+            0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+            0x64, 0xec, 0x38, 0x43,
+        ];
+
+        assert_eq!(tag(b"Jefe", b"what do ya want for nothing?"), expected);
+
+        assert!(verify(b"Jefe", b"what do ya want for nothing?", &expected));
+
+        assert!(!verify(b"Jefe", b"what do ya want for nothing!", &expected));
+    }
+}
@@ -2,12 +2,99 @@
 
 pub enum Error {
     NotFoundSubscribe,
+    OuterPathForManyResources,
+    ManyResources,
+    BrokenSection,
+    Unauthenticated,
+    NameNotStored,
+    BrokenArmor,
+    NotFileCommand,
+    SplitFile,
+    BrokenVolume(String),
+    MissingVolume(String),
+    ForeignVolume(String),
+    MisplacedVolume(String, u32),
+    BrokenParity,
+    UnrecoverableChunk(usize),
+    NotProtected,
+    UnsupportedCarrier,
+    CarrierTooSmall(usize, usize),
+    NoHiddenData,
+    CarrierImage(image::ImageError),
+    Compression(crate::realisation::compression::error::Error),
+    Output(std::io::Error),
+    Editor(std::io::Error),
+    Pattern(regex::Error),
+    NoMatch,
+    Differ,
+    Input(std::io::Error),
 }
 
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::NotFoundSubscribe => write!(f, "Подпись файла не найдена"),
+            Self::OuterPathForManyResources => {
+                write!(f, "Путь записи можно указать только для одного файла")
+            }
+            Self::ManyResources => {
+                write!(
+                    f,
+                    "Ожидался один файл, несколько файлов обрабатываются пулом"
+                )
+            }
+            Self::BrokenSection => write!(f, "Раздел метаданных поврежден или пароль неверен"),
+            Self::Unauthenticated => write!(
+                f,
+                "Код аутентификации не совпадает: пароль неверен или файл изменен"
+            ),
+            Self::NameNotStored => write!(f, "Исходное имя файла не сохранено в заголовке"),
+            Self::NotFileCommand => write!(f, "Команда обрабатывает строку, а не файл"),
+            Self::BrokenArmor => write!(
+                f,
+                "Текстовая обертка повреждена: неверный Base64 или контрольная сумма"
+            ),
+            Self::SplitFile => write!(
+                f,
+                "Файл разделен на тома: соберите его командой decrypt, чтобы изменить"
+            ),
+            Self::BrokenVolume(name) => write!(
+                f,
+                "Том {} поврежден: неверный заголовок или контрольная сумма",
+                name
+            ),
+            Self::MissingVolume(name) => write!(f, "Том {} не найден", name),
+            Self::ForeignVolume(name) => write!(f, "Том {} относится к другому файлу", name),
+            Self::MisplacedVolume(name, index) => write!(
+                f,
+                "Том {} содержит часть номер {}: тома переименованы или перепутаны",
+                name, index
+            ),
+            Self::BrokenParity => write!(f, "Заголовок данных восстановления поврежден"),
+            Self::UnrecoverableChunk(index) => write!(
+                f,
+                "Блок {} поврежден сильнее, чем позволяет избыточность",
+                index
+            ),
+            Self::NotProtected => write!(f, "Файл записан без данных восстановления"),
+            Self::UnsupportedCarrier => write!(
+                f,
+                "Контейнер должен быть изображением PNG с 8 битами на канал без палитры или звуком WAV в целочисленном PCM"
+            ),
+            Self::CarrierTooSmall(needed, capacity) => write!(
+                f,
+                "Контейнер слишком мал: нужно {} байт, помещается {} байт",
+                needed, capacity
+            ),
+            Self::NoHiddenData => write!(f, "В контейнере нет скрытых данных"),
+            Self::CarrierImage(e) => write!(f, "Ошибка изображения-контейнера: {}", e),
+            Self::Compression(e) => write!(f, "Ошибка сжатия: {}", e),
+            Self::Output(e) => write!(f, "Ошибка вывода: {}", e),
+            Self::Editor(e) => write!(f, "Ошибка редактирования: {}", e),
+            Self::Pattern(e) => write!(f, "Неверный шаблон поиска: {}", e),
+            Self::NoMatch => write!(f, "Совпадений не найдено"),
+            Self::Differ => write!(f, "Файлы различаются"),
+            Self::Input(e) => write!(f, "Ошибка чтения ввода: {}", e),
         }
     }
 }
@@ -16,6 +103,32 @@ impl core::error::Error for Error {
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         match self {
             Self::NotFoundSubscribe => None,
+            Self::OuterPathForManyResources => None,
+            Self::ManyResources => None,
+            Self::BrokenSection => None,
+            Self::Unauthenticated => None,
+            Self::NameNotStored => None,
+            Self::BrokenArmor => None,
+            Self::NotFileCommand => None,
+            Self::SplitFile => None,
+            Self::BrokenVolume(_) => None,
+            Self::MissingVolume(_) => None,
+            Self::ForeignVolume(_) => None,
+            Self::MisplacedVolume(..) => None,
+            Self::BrokenParity => None,
+            Self::UnrecoverableChunk(_) => None,
+            Self::NotProtected => None,
+            Self::UnsupportedCarrier => None,
+            Self::CarrierTooSmall(..) => None,
+            Self::NoHiddenData => None,
+            Self::CarrierImage(e) => Some(e),
+            Self::Compression(e) => Some(e),
+            Self::Output(e) => Some(e),
+            Self::Editor(e) => Some(e),
+            Self::Pattern(e) => Some(e),
+            Self::NoMatch => None,
+            Self::Differ => None,
+            Self::Input(e) => Some(e),
         }
     }
 }
@@ -1 +1,4 @@
 pub mod cli;
+pub mod diff;
+pub mod editor;
+pub mod output;
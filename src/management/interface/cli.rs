@@ -1,35 +1,376 @@
-#[derive(clap::Parser, Debug)]
+#[derive(clap::Parser, Debug, Clone)]
 
 pub struct Cli {
     #[command(subcommand)]
     pub command: Command,
+    ///Число потоков обработки, по умолчанию - все доступные ядра
+    #[arg(long, global = true)]
+    pub jobs: Option<usize>,
 }
 
-#[derive(clap::Subcommand, Debug)]
+#[derive(clap::Subcommand, Debug, Clone)]
 
 pub enum Command {
-    ///Шифрование файла, аргумент - путь до файла
+    ///Шифрование файлов, аргументы - пути до файлов
     Prepare {
-        path_inner: String,
+        #[arg(required = true)]
+        path_inner: Vec<String>,
         #[arg(long)]
         path_outer: Option<String>,
         #[arg(long, default_value_t = String::from(""))]
         password: String,
         #[arg(long, default_value_t = crate::realisation::encryption::CryptoFormat::XOR)]
         cipher: crate::realisation::encryption::CryptoFormat,
+        ///Формат исходного файла (имя или расширение, например pdf), если он определяется неверно
+        #[arg(long)]
+        format: Option<String>,
+        ///Алгоритм сжатия перед шифрованием, по умолчанию выбирается по формату файла
+        #[arg(long)]
+        compress: Option<crate::realisation::compression::CompressionFormat>,
+        ///Дополнение, скрывающее размер: padme, bucket, block:<байт> или none
+        #[arg(long, default_value_t = crate::realisation::padding::PaddingScheme::None)]
+        pad: crate::realisation::padding::PaddingScheme,
+        ///Сохранить результат под случайным именем, исходное имя хранится в заголовке
+        #[arg(long, conflicts_with = "path_outer")]
+        obfuscate_name: bool,
+        ///Записать результат текстом: Base64 в рамке BEGIN/END с контрольной суммой
+        #[arg(long)]
+        armor: bool,
+        ///Разделить результат на тома name.001, name.002, ... не больше заданного размера (например 100M)
+        #[arg(long, value_parser = parse_size, conflicts_with_all = ["armor", "obfuscate_name"])]
+        split: Option<usize>,
+        ///Добавить данные восстановления Рида-Соломона, процент от размера (например 10%)
+        #[arg(long, value_parser = parse_percent)]
+        parity: Option<u8>,
+        ///Контейнер PNG или WAV, в который встраивается результат (задается командой hide)
+        #[arg(skip)]
+        carrier: Option<String>,
     },
     ///Чтение файла, аргумент - путь до файла
     Read {
         path: String,
         #[arg(long, default_value_t = String::from(""))]
         password: String,
+        #[command(flatten)]
+        view: View,
     },
-    ///Расшифровка файла, аргумент - путь до файла
+    ///Расшифровка файлов, аргументы - пути до файлов
     Decrypt {
-        path_inner: String,
+        #[arg(required = true)]
+        path_inner: Vec<String>,
         #[arg(long)]
         path_outer: Option<String>,
         #[arg(long, default_value_t = String::from(""))]
         password: String,
+        ///Восстановить права, время, владельца и атрибуты исходного файла (по умолчанию)
+        #[arg(long, overrides_with = "no_preserve")]
+        preserve: bool,
+        ///Не восстанавливать метаданные исходного файла
+        #[arg(long, overrides_with = "preserve")]
+        no_preserve: bool,
+        ///Переименовать результат в исходное имя, сохраненное в заголовке
+        #[arg(long)]
+        restore_name: bool,
+        ///Зашифрованный файл встроен в контейнер PNG или WAV (задается командой reveal)
+        #[arg(skip)]
+        carrier: bool,
+    },
+    ///Шифрование файла и встраивание в младшие биты пикселей PNG или отсчетов WAV
+    Hide {
+        path: String,
+        ///Изображение PNG или звук WAV (PCM), в который прячется файл
+        #[arg(long)]
+        carrier: String,
+        ///Куда записать контейнер с файлом, по умолчанию на место исходного контейнера
+        #[arg(long)]
+        path_outer: Option<String>,
+        #[arg(long, default_value_t = String::from(""))]
+        password: String,
+        #[arg(long, default_value_t = crate::realisation::encryption::CryptoFormat::XOR)]
+        cipher: crate::realisation::encryption::CryptoFormat,
+        ///Дополнение, скрывающее размер: padme, bucket, block:<байт> или none
+        #[arg(long, default_value_t = crate::realisation::padding::PaddingScheme::None)]
+        pad: crate::realisation::padding::PaddingScheme,
+    },
+    ///Извлечение и расшифровка файла, спрятанного командой hide
+    Reveal {
+        carrier: String,
+        ///Куда записать файл, по умолчанию исходное имя рядом с контейнером
+        #[arg(long)]
+        path_outer: Option<String>,
+        #[arg(long, default_value_t = String::from(""))]
+        password: String,
+    },
+    ///Восстановление поврежденных файлов по данным избыточности и перезапись исправленных
+    Repair {
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    ///Редактирование зашифрованного файла в $EDITOR с повторным шифрованием при сохранении
+    Edit {
+        path: String,
+        #[arg(long, default_value_t = String::from(""))]
+        password: String,
+        ///Дополнение при повторном шифровании, по умолчанию схема, с которой файл был записан
+        #[arg(long)]
+        pad: Option<crate::realisation::padding::PaddingScheme>,
+    },
+    ///Поиск строк в зашифрованных файлах без записи расшифрованных данных на диск
+    Grep {
+        ///Регулярное выражение, с --fixed-strings - обычная строка
+        pattern: String,
+        #[arg(required = true)]
+        paths: Vec<String>,
+        #[arg(long, default_value_t = String::from(""))]
+        password: String,
+        ///Искать шаблон как обычную строку
+        #[arg(short = 'F', long)]
+        fixed_strings: bool,
+        ///Не различать регистр букв
+        #[arg(short, long)]
+        ignore_case: bool,
+        ///Выводить только число совпавших строк в каждом файле
+        #[arg(short, long)]
+        count: bool,
+        ///Выводить номер строки извлеченного текста
+        #[arg(short = 'n', long)]
+        line_number: bool,
     },
+    ///Сравнение двух зашифрованных файлов: текст - единым diff, двоичные данные - сводкой
+    Diff {
+        a: String,
+        b: String,
+        #[arg(long, default_value_t = String::from(""))]
+        password: String,
+        ///Пароль второго файла, по умолчанию совпадает с --password
+        #[arg(long)]
+        password_b: Option<String>,
+        ///Число строк контекста вокруг изменений
+        #[arg(short = 'U', long, default_value_t = 3)]
+        context: usize,
+    },
+    ///Шифрование строки из аргумента или stdin, результат - текст в обертке Base64
+    EncryptText {
+        ///Строка для шифрования, без нее читается stdin без завершающего перевода строки
+        #[arg(allow_hyphen_values = true)]
+        text: Option<String>,
+        #[arg(long, default_value_t = String::from(""))]
+        password: String,
+        #[arg(long, default_value_t = crate::realisation::encryption::CryptoFormat::XOR)]
+        cipher: crate::realisation::encryption::CryptoFormat,
+        ///Дополнение, скрывающее длину строки: padme, bucket, block:<байт> или none
+        #[arg(long, default_value_t = crate::realisation::padding::PaddingScheme::None)]
+        pad: crate::realisation::padding::PaddingScheme,
+    },
+    ///Расшифровка строки в обертке Base64 из аргумента или stdin
+    DecryptText {
+        ///Зашифрованный текст, без него читается stdin
+        #[arg(allow_hyphen_values = true)]
+        text: Option<String>,
+        #[arg(long, default_value_t = String::from(""))]
+        password: String,
+    },
+    ///Сведения о зашифрованном файле, исходное имя видно только с верным паролем
+    Info {
+        path: String,
+        #[arg(long, default_value_t = String::from(""))]
+        password: String,
+    },
+}
+
+/// Параметры отображения команды `read`
+#[derive(clap::Args, Debug, Clone)]
+
+pub struct View {
+    ///Лист электронной таблицы по имени или номеру, начиная с 1
+    #[arg(long)]
+    pub sheet: Option<String>,
+    ///Максимум строк таблицы, 0 - без ограничения
+    #[arg(long, default_value_t = 50)]
+    pub max_rows: usize,
+    ///Максимум столбцов таблицы, 0 - без ограничения
+    #[arg(long, default_value_t = 20)]
+    pub max_cols: usize,
+    ///Вывести лист таблицы целиком в формате CSV
+    #[arg(long)]
+    pub csv: bool,
+    ///Показать уменьшенное изображение в терминале (нужна поддержка 24-битного цвета)
+    #[arg(long)]
+    pub preview: bool,
+    ///Ширина предпросмотра изображения в символах
+    #[arg(long, default_value_t = 48)]
+    pub preview_width: usize,
+    ///Показать файл внутри архива (полное имя из оглавления) вместо оглавления
+    #[arg(long)]
+    pub member: Option<String>,
+    ///Вывести одно значение JSON, YAML, TOML или XML по пути, например database.password
+    #[arg(long)]
+    pub query: Option<String>,
+    ///Подсветить синтаксис структурированных данных цветом
+    #[arg(long)]
+    pub color: bool,
+    ///Показать данные шестнадцатеричным дампом в стиле xxd независимо от формата
+    #[arg(long)]
+    pub hex: bool,
+    ///Смещение начала дампа в байтах (десятичное или 0x...)
+    #[arg(long, default_value_t = 0, value_parser = parse_number)]
+    pub offset: usize,
+    ///Длина дампа в байтах (десятичная или 0x...), 0 - до конца данных
+    #[arg(long, value_parser = parse_number)]
+    pub length: Option<usize>,
+    ///Постраничный просмотр через $PAGER (по умолчанию less -R)
+    #[arg(long)]
+    pub page: bool,
+    ///Вывести представление данных в JSON: заголовки, текст, таблицы и поля
+    #[arg(long, conflicts_with = "page")]
+    pub json: bool,
+}
+
+/// Разбирает число в десятичной или шестнадцатеричной (`0x...`) записи
+
+fn parse_number(value: &str) -> Result<usize, String> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => value.parse(),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Разбирает размер в байтах с необязательным двоичным суффиксом: `512K`, `100M`, `1G`, `2TiB`
+
+fn parse_size(value: &str) -> Result<usize, String> {
+    let upper = value.trim().to_ascii_uppercase();
+
+    let digits = upper.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+
+    let shift = match &upper[digits.len()..] {
+        "" | "B" => 0,
+        "K" | "KB" | "KIB" => 10,
+        "M" | "MB" | "MIB" => 20,
+        "G" | "GB" | "GIB" => 30,
+        "T" | "TB" | "TIB" => 40,
+        suffix => return Err(format!("Неизвестный суффикс размера '{}'", suffix)),
+    };
+
+    let size = digits
+        .parse::<usize>()
+        .map_err(|e| e.to_string())?
+        .checked_mul(1 << shift)
+        .ok_or_else(|| String::from("Размер слишком велик"))?;
+
+    if size <= crate::management::volume::HEADER_SIZE {
+        return Err(format!(
+            "Том должен быть больше заголовка тома ({} байт)",
+            crate::management::volume::HEADER_SIZE
+        ));
+    }
+
+    Ok(size)
+}
+
+/// Разбирает процент избыточности от 1 до 100, знак `%` необязателен
+
+fn parse_percent(value: &str) -> Result<u8, String> {
+    let percent = value
+        .trim()
+        .trim_end_matches('%')
+        .parse::<u8>()
+        .map_err(|e| e.to_string())?;
+
+    if !(1..=100).contains(&percent) {
+        return Err(String::from("Избыточность задается от 1% до 100%"));
+    }
+
+    Ok(percent)
+}
+
+impl View {
+    pub fn options(&self) -> crate::abstraction::ViewOptions {
+        crate::abstraction::ViewOptions {
+            sheet: self.sheet.clone(),
+            max_rows: self.max_rows,
+            max_cols: self.max_cols,
+            csv: self.csv,
+            preview: self.preview,
+            preview_width: self.preview_width,
+            member: self.member.clone(),
+            query: self.query.clone(),
+            color: self.color,
+            hex: self.hex,
+            offset: self.offset,
+            length: self.length,
+        }
+    }
+}
+
+impl Cli {
+    /// Итоговое число потоков обработки
+
+    pub fn jobs(&self) -> usize {
+        self.jobs
+            .unwrap_or_else(crate::management::pool::available_jobs)
+            .max(1)
+    }
+
+    /// Разбивает команду над несколькими файлами на независимые команды по одному файлу.
+    ///
+    /// Потоки делятся между файлами, чтобы не создавать больше потоков, чем задано.
+
+    pub fn split(&self) -> Result<Vec<Cli>, crate::management::error::Error> {
+        let (paths, path_outer) = match &self.command {
+            Command::Prepare {
+                path_inner,
+                path_outer,
+                ..
+            }
+            | Command::Decrypt {
+                path_inner,
+                path_outer,
+                ..
+            } => (path_inner, path_outer.as_ref()),
+            Command::Grep { paths, .. } | Command::Repair { paths } => (paths, None),
+            Command::Read { .. }
+            | Command::Edit { .. }
+            | Command::Diff { .. }
+            | Command::EncryptText { .. }
+            | Command::DecryptText { .. }
+            | Command::Hide { .. }
+            | Command::Reveal { .. }
+            | Command::Info { .. } => {
+                return Ok(vec![self.clone()]);
+            }
+        };
+
+        if paths.len() > 1 && path_outer.is_some() {
+            return Err(crate::management::error::Error::OuterPathForManyResources);
+        }
+
+        let jobs = (self.jobs() / paths.len()).max(1);
+
+        Ok(paths
+            .iter()
+            .map(|path| {
+                let mut cli = self.clone();
+
+                cli.jobs = Some(jobs);
+
+                if let Command::Prepare {
+                    path_inner: paths, ..
+                }
+                | Command::Decrypt {
+                    path_inner: paths, ..
+                }
+                | Command::Grep { paths, .. }
+                | Command::Repair { paths } = &mut cli.command
+                {
+                    *paths = vec![path.clone()];
+                }
+
+                cli
+            })
+            .collect())
+    }
 }
@@ -0,0 +1,87 @@
+/// Сколько различающихся диапазонов двоичных данных показывать
+
+const MAX_RANGES: usize = 20;
+
+/// Единый (unified) diff строк текста, пустой для одинакового текста
+
+pub fn text(names: (&str, &str), old: &[String], new: &[String], context: usize) -> Vec<String> {
+    let (old, new) = (joined(old), joined(new));
+
+    let diff = similar::TextDiff::from_lines(&old, &new);
+
+    diff.unified_diff()
+        .context_radius(context)
+        .header(names.0, names.1)
+        .to_string()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+/// Сводка различий двоичных данных: диапазоны различающихся байт и размеры.
+///
+/// Байты сравниваются по одинаковым смещениям, без поиска вставок и удалений.
+
+pub fn bytes(names: (&str, &str), old: &[u8], new: &[u8]) -> Vec<String> {
+    if old == new {
+        return Vec::new();
+    }
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+
+    for (index, _) in old.iter().zip(new).enumerate().filter(|(_, (a, b))| a != b) {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == index => *end += 1,
+            _ => ranges.push((index, index + 1)),
+        }
+    }
+
+    let common = old.len().min(new.len());
+
+    // Хвост более длинного файла целиком считается отличием
+    if old.len() != new.len() {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == common => *end = old.len().max(new.len()),
+            _ => ranges.push((common, old.len().max(new.len()))),
+        }
+    }
+
+    let changed: usize = ranges.iter().map(|(start, end)| end - start).sum();
+
+    let mut lines = vec![
+        format!("Двоичные файлы {} и {} различаются", names.0, names.1),
+        format!("Размер: {} -> {} байт", old.len(), new.len()),
+        format!(
+            "Различающихся байт: {} в {} диапазонах",
+            changed,
+            ranges.len()
+        ),
+    ];
+
+    lines.extend(
+        ranges
+            .iter()
+            .take(MAX_RANGES)
+            .map(|(start, end)| format!("  {:#010x}..{:#010x}  {} байт", start, end, end - start)),
+    );
+
+    if ranges.len() > MAX_RANGES {
+        lines.push(format!(
+            "  (показано диапазонов: {} из {})",
+            MAX_RANGES,
+            ranges.len()
+        ));
+    }
+
+    lines
+}
+
+fn joined(lines: &[String]) -> String {
+    let mut text = lines.join("\n");
+
+    if !text.is_empty() {
+        text.push('\n');
+    }
+
+    text
+}
@@ -0,0 +1,180 @@
+use std::io::{Read, Write};
+
+/// Общий каталог в оперативной памяти (tmpfs), где временная копия не попадает на диск
+
+const SHARED_MEMORY: &str = "/dev/shm";
+
+/// Редактор, если `$EDITOR` не задан
+
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Открывает копию данных в `$EDITOR` и возвращает измененные данные.
+///
+/// Копия создается с правами 0600 в закрытом каталоге, по возможности в tmpfs,
+/// под исходным именем, чтобы редактор узнал формат. После выхода из редактора
+/// каталог затирается и удаляется, в том числе при ошибке.
+/// `None` - данные не изменились.
+
+pub fn edit(name: &str, data: &[u8]) -> std::io::Result<Option<Vec<u8>>> {
+    let dir = private_dir()?;
+
+    let result = run(&dir, name, data);
+
+    let removed = wipe(&dir);
+
+    let edited = result?;
+
+    removed?;
+
+    Ok(Some(edited).filter(|edited| edited != data))
+}
+
+fn run(dir: &std::path::Path, name: &str, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    // Имя без каталогов: сохраненное имя не должно выводить копию за пределы каталога
+    let name = std::path::Path::new(name)
+        .file_name()
+        .filter(|name| !name.is_empty())
+        .unwrap_or(std::ffi::OsStr::new("data"));
+
+    let path = dir.join(name);
+
+    let mut file = create(&path)?;
+
+    file.write_all(data)?;
+
+    file.sync_all()?;
+
+    drop(file);
+
+    let editor = std::env::var("EDITOR")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| String::from(DEFAULT_EDITOR));
+
+    let mut words = editor.split_whitespace();
+
+    let program = words.next().unwrap_or(DEFAULT_EDITOR);
+
+    let status = std::process::Command::new(program)
+        .args(words)
+        .arg(&path)
+        .status()
+        .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", program, e)))?;
+
+    if !status.success() {
+        return Err(std::io::Error::other(format!(
+            "{} завершился с ошибкой ({}), файл не изменен",
+            program, status
+        )));
+    }
+
+    std::fs::read(&path)
+}
+
+/// Создает каталог со случайным именем и правами 0700, сначала в tmpfs
+
+fn private_dir() -> std::io::Result<std::path::PathBuf> {
+    let name = rand::random::<[u8; 16]>()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    let user_dir = std::env::var_os("XDG_RUNTIME_DIR").map(std::path::PathBuf::from);
+
+    let parents = user_dir
+        .into_iter()
+        .chain(std::iter::once(std::path::PathBuf::from(SHARED_MEMORY)))
+        .filter(|dir| is_memory(dir))
+        .chain(std::iter::once(std::env::temp_dir()));
+
+    let mut last = None;
+
+    for parent in parents {
+        let dir = parent.join(format!("de-en-coder-{}", name));
+
+        let mut builder = std::fs::DirBuilder::new();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+
+            builder.mode(0o700);
+        }
+
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) => last = Some(e),
+        }
+    }
+
+    Err(last.unwrap_or_else(|| std::io::Error::other("нет каталога для временной копии")))
+}
+
+/// Находится ли каталог в файловой системе в памяти (tmpfs, ramfs)
+
+fn is_memory(dir: &std::path::Path) -> bool {
+    if !dir.is_dir() {
+        return false;
+    }
+
+    let Ok(mounts) = std::fs::read_to_string("/proc/self/mounts") else {
+        // Без сведений о точках монтирования доверяем только /dev/shm
+        return dir == std::path::Path::new(SHARED_MEMORY);
+    };
+
+    let dir = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+
+    // Точка монтирования с самым длинным префиксом определяет файловую систему каталога
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+
+            let point = fields.next().and(fields.next())?;
+
+            let kind = fields.next()?;
+
+            dir.starts_with(point).then_some((point.len(), kind))
+        })
+        .max_by_key(|(len, _)| *len)
+        .is_some_and(|(_, kind)| matches!(kind, "tmpfs" | "ramfs"))
+}
+
+fn create(path: &std::path::Path) -> std::io::Result<std::fs::File> {
+    let mut options = std::fs::OpenOptions::new();
+
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+
+    options.open(path)
+}
+
+/// Затирает нулями все файлы каталога, включая резервные копии редактора, и удаляет его.
+///
+/// Файл, который не удалось затереть, все равно удаляется вместе с каталогом.
+
+fn wipe(dir: &std::path::Path) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        if entry.file_type().is_ok_and(|kind| kind.is_file()) {
+            let _ = overwrite(&entry.path());
+        }
+    }
+
+    std::fs::remove_dir_all(dir)
+}
+
+fn overwrite(path: &std::path::Path) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+
+    let len = file.metadata()?.len();
+
+    std::io::copy(&mut std::io::repeat(0).take(len), &mut file)?;
+
+    file.sync_all()
+}
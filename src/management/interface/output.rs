@@ -0,0 +1,187 @@
+use crate::abstraction::Block;
+use std::io::{IsTerminal, Write};
+
+/// Максимальная ширина ячейки при выводе выровненной таблицы
+
+const MAX_CELL_WIDTH: usize = 40;
+
+/// Черта, завершающая вывод с заголовком
+
+const RULE: &str = "----------------------------";
+
+/// Строки для терминала: заголовки в рамке `---`, таблицы выровнены по столбцам.
+///
+/// Вывод, начинающийся с заголовка, завершается чертой. Вывод без заголовка
+/// (значение `--query`, `--csv`) печатается как есть, чтобы его можно было передать другой программе.
+
+pub fn text(blocks: &[Block]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for block in blocks {
+        match block {
+            Block::Heading(heading) => lines.push(format!("--- {} ---", heading)),
+            Block::Text(text) => lines.extend(text.lines().map(String::from)),
+            Block::Table(rows) => lines.extend(table(rows)),
+            Block::Fields(fields) => lines.extend(
+                fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value)),
+            ),
+            Block::Note(note) => lines.push(format!("({})", note)),
+            Block::Error(e) => lines.push(e.clone()),
+        }
+    }
+
+    if matches!(blocks.first(), Some(Block::Heading(_))) {
+        lines.push(String::from(RULE));
+    }
+
+    lines
+}
+
+/// Содержимое без оформления для поиска: текст, строки таблиц через табуляцию и поля.
+///
+/// Заголовки, примечания и ошибки к содержимому не относятся и опускаются.
+
+pub fn content(blocks: &[Block]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for block in blocks {
+        match block {
+            Block::Text(text) => lines.extend(text.lines().map(String::from)),
+            Block::Table(rows) => lines.extend(rows.iter().map(|row| row.join("\t"))),
+            Block::Fields(fields) => lines.extend(
+                fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value)),
+            ),
+            Block::Heading(_) | Block::Note(_) | Block::Error(_) => {}
+        }
+    }
+
+    lines
+}
+
+/// Представление в JSON: массив элементов с полем `type`
+
+pub fn json(blocks: &[Block]) -> String {
+    let blocks: Vec<serde_json::Value> = blocks
+        .iter()
+        .map(|block| match block {
+            Block::Heading(text) => serde_json::json!({ "type": "heading", "text": text }),
+            Block::Text(text) => serde_json::json!({ "type": "text", "text": text }),
+            Block::Table(rows) => serde_json::json!({ "type": "table", "rows": rows }),
+            Block::Fields(fields) => serde_json::json!({
+                "type": "fields",
+                "fields": fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), serde_json::Value::from(value.as_str())))
+                    .collect::<serde_json::Map<_, _>>(),
+            }),
+            Block::Note(text) => serde_json::json!({ "type": "note", "text": text }),
+            Block::Error(text) => serde_json::json!({ "type": "error", "text": text }),
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&blocks).unwrap_or_default()
+}
+
+/// Печатает строки, при `page` - через программу постраничного просмотра
+/// (`$PAGER`, по умолчанию `less -R`).
+///
+/// Если вывод не в терминал или программу запустить не удалось, строки печатаются как есть.
+/// Закрытый раньше конца вывода канал (`| head`, выход из просмотра) ошибкой не считается.
+
+pub fn print(lines: &[String], page: bool) -> std::io::Result<()> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| String::from("less -R"));
+
+    let mut words = pager.split_whitespace();
+
+    let child = match words.next() {
+        Some(program) if page && std::io::stdout().is_terminal() => {
+            std::process::Command::new(program)
+                .args(words)
+                .stdin(std::process::Stdio::piped())
+                .spawn()
+                .ok()
+        }
+        _ => None,
+    };
+
+    let result = match child {
+        Some(mut child) => {
+            let written = match child.stdin.take() {
+                Some(mut stdin) => write_lines(&mut stdin, lines),
+                None => Ok(()),
+            };
+
+            written.and(child.wait().map(|_| ()))
+        }
+        None => write_lines(&mut std::io::stdout().lock(), lines),
+    };
+
+    match result {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+fn write_lines(out: &mut impl Write, lines: &[String]) -> std::io::Result<()> {
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+
+    out.flush()
+}
+
+/// Выровненная таблица, первая строка отделяется чертой как заголовок
+
+fn table(rows: &[Vec<String>]) -> Vec<String> {
+    let cell = |row: &Vec<String>, col: usize| -> String {
+        let text = row
+            .get(col)
+            .map_or("", String::as_str)
+            .replace(['\n', '\r', '\t'], " ");
+
+        if text.chars().count() > MAX_CELL_WIDTH {
+            let mut cut: String = text.chars().take(MAX_CELL_WIDTH - 1).collect();
+
+            cut.push('…');
+
+            cut
+        } else {
+            text
+        }
+    };
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .map(|row| cell(row, col).chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+
+    for (index, row) in rows.iter().enumerate() {
+        let line: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(col, width)| format!("{:<width$}", cell(row, col), width = *width))
+            .collect();
+
+        lines.push(line.join(" | ").trim_end().to_string());
+
+        if index == 0 && rows.len() > 1 {
+            let line: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+
+            lines.push(line.join("-+-"));
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,235 @@
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use sha2::Digest;
+
+/// Сигнатура файла с данными восстановления
+
+const MAGIC: &[u8; 8] = b"DEENFEC\0";
+
+/// Версия формата данных восстановления
+
+const VERSION: u8 = 1;
+
+/// Размер заголовка: сигнатура, версия, процент избыточности,
+/// длина защищенных данных и контрольная сумма самого заголовка.
+///
+/// Заголовок записывается в начале и в конце файла, чтобы пережить повреждение одной копии.
+
+const HEADER_SIZE: usize = MAGIC.len() + 1 + 1 + 8 + CHECKSUM_SIZE;
+
+/// Размер фрагмента, повреждение проверяется и исправляется фрагментами
+
+const SHARD_SIZE: usize = 1024;
+
+/// Фрагментов данных в блоке, вместе с фрагментами избыточности не больше 256
+
+const DATA_SHARDS: usize = 128;
+
+/// Данных в одном блоке
+
+const CHUNK_SIZE: usize = SHARD_SIZE * DATA_SHARDS;
+
+/// Контрольная сумма фрагмента: первые байты SHA-256
+
+const CHECKSUM_SIZE: usize = 4;
+
+/// Данные, восстановленные из защищенного файла
+
+#[derive(Debug)]
+
+pub struct Decoded {
+    pub data: Vec<u8>,
+    /// Процент избыточности из заголовка
+    pub parity: u8,
+    /// Число поврежденных фрагментов, найденных при чтении
+    pub repaired: usize,
+}
+
+/// Защищает данные кодом Рида-Соломона: на каждые 100 фрагментов блока
+/// добавляется `parity` фрагментов избыточности (с округлением вверх)
+
+pub fn encode(data: &[u8], parity: u8) -> Vec<u8> {
+    let header = header(parity, data.len());
+
+    let mut out = Vec::with_capacity(data.len() * (100 + parity as usize) / 100 + 4 * HEADER_SIZE);
+
+    out.extend_from_slice(&header);
+
+    for chunk in data.chunks(CHUNK_SIZE) {
+        let mut shards: Vec<Vec<u8>> = chunk
+            .chunks(SHARD_SIZE)
+            .map(|shard| {
+                let mut shard = shard.to_vec();
+
+                shard.resize(SHARD_SIZE, 0);
+
+                shard
+            })
+            .collect();
+
+        let parity_shards = parity_shards(shards.len(), parity);
+
+        shards.resize(shards.len() + parity_shards, vec![0u8; SHARD_SIZE]);
+
+        ReedSolomon::new(shards.len() - parity_shards, parity_shards)
+            .and_then(|rs| rs.encode(&mut shards))
+            .expect("число фрагментов блока в пределах поля GF(2^8)");
+
+        for shard in &shards {
+            out.extend_from_slice(shard);
+
+            out.extend_from_slice(&checksum(shard));
+        }
+    }
+
+    out.extend_from_slice(&header);
+
+    out
+}
+
+/// Есть ли у данных заголовок избыточности в начале или в конце
+
+pub fn is_protected(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+        || bytes.len() >= HEADER_SIZE && bytes[bytes.len() - HEADER_SIZE..].starts_with(MAGIC)
+}
+
+/// Проверяет фрагменты по контрольным суммам и восстанавливает поврежденные.
+///
+/// Ошибка - номер блока (с 1), поврежденного сильнее, чем позволяет избыточность,
+/// или `None`, если не читается ни одна копия заголовка.
+
+pub fn decode(bytes: &[u8]) -> Result<Decoded, Option<usize>> {
+    let tail = bytes.len().saturating_sub(HEADER_SIZE);
+
+    let (parity, len) = bytes
+        .get(..HEADER_SIZE)
+        .and_then(parse_header)
+        .or_else(|| parse_header(&bytes[tail..]))
+        .ok_or(None)?;
+
+    let stored = SHARD_SIZE + CHECKSUM_SIZE;
+
+    // Длина из заголовка не может превышать того, что вмещают фрагменты файла,
+    // иначе подделанный заголовок заставил бы выделить сколько угодно памяти
+    let capacity = bytes.len().saturating_sub(2 * HEADER_SIZE) / stored * SHARD_SIZE;
+
+    if len > capacity {
+        return Err(None);
+    }
+
+    let mut data = Vec::with_capacity(len);
+
+    let mut offset = HEADER_SIZE;
+
+    let mut repaired = 0;
+
+    for (index, start) in (0..len).step_by(CHUNK_SIZE).enumerate() {
+        let data_shards = (len - start).min(CHUNK_SIZE).div_ceil(SHARD_SIZE);
+
+        let parity_shards = parity_shards(data_shards, parity);
+
+        // Обрезанный файл читается как набор потерянных фрагментов
+        let mut shards: Vec<Option<Vec<u8>>> = (0..data_shards + parity_shards)
+            .map(|shard| {
+                let at = offset + shard * stored;
+
+                let (shard, sum) = bytes.get(at..at + stored)?.split_at(SHARD_SIZE);
+
+                (checksum(shard) == sum).then(|| shard.to_vec())
+            })
+            .collect();
+
+        offset += shards.len() * stored;
+
+        let damaged = shards.iter().filter(|shard| shard.is_none()).count();
+
+        if damaged > 0 {
+            ReedSolomon::new(data_shards, parity_shards)
+                .and_then(|rs| rs.reconstruct_data(&mut shards))
+                .map_err(|_| Some(index + 1))?;
+
+            repaired += damaged;
+        }
+
+        for shard in shards.into_iter().take(data_shards).flatten() {
+            data.extend_from_slice(&shard);
+        }
+    }
+
+    data.truncate(len);
+
+    Ok(Decoded {
+        data,
+        parity,
+        repaired,
+    })
+}
+
+fn parity_shards(data_shards: usize, parity: u8) -> usize {
+    (data_shards * parity as usize).div_ceil(100)
+}
+
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    let digest = sha2::Sha256::digest(bytes);
+
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+fn header(parity: u8, len: usize) -> [u8; HEADER_SIZE] {
+    let mut header = [0u8; HEADER_SIZE];
+
+    header[..MAGIC.len()].copy_from_slice(MAGIC);
+
+    header[MAGIC.len()] = VERSION;
+
+    header[MAGIC.len() + 1] = parity;
+
+    header[MAGIC.len() + 2..HEADER_SIZE - CHECKSUM_SIZE]
+        .copy_from_slice(&(len as u64).to_le_bytes());
+
+    let sum = checksum(&header[..HEADER_SIZE - CHECKSUM_SIZE]);
+
+    header[HEADER_SIZE - CHECKSUM_SIZE..].copy_from_slice(&sum);
+
+    header
+}
+
+fn parse_header(bytes: &[u8]) -> Option<(u8, usize)> {
+    if bytes.len() != HEADER_SIZE {
+        return None;
+    }
+
+    let (fields, sum) = bytes.split_at(HEADER_SIZE - CHECKSUM_SIZE);
+
+    if !fields.starts_with(MAGIC) || fields[MAGIC.len()] != VERSION || checksum(fields) != sum {
+        return None;
+    }
+
+    let parity = fields[MAGIC.len() + 1];
+
+    let len = u64::from_le_bytes(fields[MAGIC.len() + 2..].try_into().ok()?);
+
+    (1..=100)
+        .contains(&parity)
+        .then_some((parity, usize::try_from(len).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forged_length_is_rejected() {
+        let mut bytes = encode(&[7u8; 5000], 10);
+
+        let forged = header(10, 1 << 60);
+
+        let tail = bytes.len() - HEADER_SIZE;
+
+        bytes[..HEADER_SIZE].copy_from_slice(&forged);
+
+        bytes[tail..].copy_from_slice(&forged);
+
+        assert!(matches!(decode(&bytes), Err(None)));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::abstraction::Encryption;
+
+/// Минимальный размер фрагмента, ради которого стоит запускать отдельный поток
+
+const MIN_CHUNK_SIZE: usize = 1 << 20;
+
+/// Число потоков по умолчанию - все доступные ядра
+
+pub fn available_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+}
+
+/// Выполняет независимые задачи в пуле из `jobs` потоков.
+///
+/// Возвращает первую по порядку задач ошибку, остальные задачи при этом
+/// доводятся до конца.
+
+pub fn for_each<T, E, F>(tasks: Vec<T>, jobs: usize, f: F) -> Result<(), E>
+where
+    T: Send,
+    E: Send,
+    F: Fn(T) -> Result<(), E> + Sync,
+{
+    let total = tasks.len();
+
+    let queue = std::sync::Mutex::new(tasks.into_iter().enumerate());
+
+    let results = std::sync::Mutex::new(Vec::with_capacity(total));
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, total.max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let task = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+
+                    let Some((index, task)) = task else {
+                        break;
+                    };
+
+                    let result = f(task);
+
+                    results
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push((index, result));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+
+    results.sort_by_key(|(index, _)| *index);
+
+    results.into_iter().try_for_each(|(_, result)| result)
+}
+
+/// Шифрует буфер, начинающийся со смещения `offset` в потоке шифра,
+/// фрагментами параллельно, если шифр допускает произвольный доступ.
+///
+/// Результат побайтно совпадает с однопоточным `encode_at`.
+
+pub fn encode<E>(cipher: &E, buf: &mut [u8], offset: usize, jobs: usize)
+where
+    E: Encryption + Sync,
+{
+    match chunk_size(cipher, buf.len(), jobs) {
+        Some(chunk) => std::thread::scope(|scope| {
+            for (index, part) in buf.chunks_mut(chunk).enumerate() {
+                scope.spawn(move || cipher.encode_at(part, offset + index * chunk));
+            }
+        }),
+        None => cipher.encode_at(buf, offset),
+    }
+}
+
+/// Дешифрует буфер, начинающийся со смещения `offset` в потоке шифра,
+/// фрагментами параллельно, если шифр допускает произвольный доступ.
+
+pub fn decode<E>(cipher: &E, buf: &mut [u8], offset: usize, jobs: usize)
+where
+    E: Encryption + Sync,
+{
+    match chunk_size(cipher, buf.len(), jobs) {
+        Some(chunk) => std::thread::scope(|scope| {
+            for (index, part) in buf.chunks_mut(chunk).enumerate() {
+                scope.spawn(move || cipher.decode_at(part, offset + index * chunk));
+            }
+        }),
+        None => cipher.decode_at(buf, offset),
+    }
+}
+
+/// Размер фрагмента для параллельной обработки, `None` - обрабатывать целиком
+
+fn chunk_size<E: Encryption>(cipher: &E, len: usize, jobs: usize) -> Option<usize> {
+    if jobs < 2 || !cipher.seekable() || len < 2 * MIN_CHUNK_SIZE {
+        return None;
+    }
+
+    Some(len.div_ceil(jobs).max(MIN_CHUNK_SIZE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_encode_matches_single_thread() {
+        // Длина ключа не делит размер фрагмента, ошибка в смещении фрагмента
+        // сдвинула бы поток шифра
+        let cipher = crate::realisation::encryption::xor::XorEncryption::new((1..=31).collect());
+
+        let data: Vec<u8> = (0..(5 << 20) + 123).map(|i| (i % 251) as u8).collect();
+
+        let (mut single, mut parallel) = (data.clone(), data.clone());
+
+        encode(&cipher, &mut single, 37, 1);
+
+        encode(&cipher, &mut parallel, 37, 4);
+
+        assert!(single == parallel);
+
+        decode(&cipher, &mut parallel, 37, 4);
+
+        assert!(parallel == data);
+    }
+}
@@ -28,6 +28,16 @@ where
         U::new(self.inner.clone(), crate::abstraction::Operation::Open)
             .map_err(|e| error::Error::ResourcePathError(Box::new(e)))
     }
+
+    fn out_path(&self) -> Option<&U::Path> {
+        self.out.as_ref()
+    }
+
+    fn in_place(&self) -> bool {
+        self.out
+            .as_ref()
+            .is_some_and(|out| crate::abstraction::ResourcePath::same_resource(out, &self.inner))
+    }
 }
 
 impl<U> crate::abstraction::Writer for Router<U>
@@ -50,6 +60,24 @@ where
             .write(buf)
             .map_err(|e| Self::Error::WriterError(Box::new(e)))
     }
+
+    fn write_with(
+        &mut self,
+        len: usize,
+        fill: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<usize, Self::Error> {
+        let out = match self.out {
+            Some(ref out) => out,
+            None => return Err(Self::Error::BadWriteError),
+        };
+
+        let mut resource = U::new(out.clone(), crate::abstraction::Operation::Create)
+            .map_err(|e| Self::Error::ResourcePathError(Box::new(e)))?;
+
+        resource
+            .write_with(len, fill)
+            .map_err(|e| Self::Error::WriterError(Box::new(e)))
+    }
 }
 
 impl<U> crate::abstraction::Reader for Router<U>
@@ -59,11 +87,21 @@ where
 {
     type Error = error::Error;
 
-    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+    fn read_to_end(&mut self) -> Result<crate::abstraction::Data, Self::Error> {
         let mut inr = U::new(self.inner.clone(), crate::abstraction::Operation::Open)
             .map_err(|e| Self::Error::ResourcePathError(Box::new(e)))?;
 
-        inr.read(buf)
-            .map_err(|e| Self::Error::ReaderError(Box::new(e)))
+        let data = inr
+            .read_to_end()
+            .map_err(|e| Self::Error::ReaderError(Box::new(e)))?;
+
+        // Запись на место исходного ресурса усекает его, отображенные в память
+        // страницы при этом обнулились бы, поэтому данные копируются заранее
+        match data {
+            crate::abstraction::Data::Mapped(_) if crate::abstraction::Router::in_place(self) => {
+                Ok(data.into_vec().into())
+            }
+            data => Ok(data),
+        }
     }
 }
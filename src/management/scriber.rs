@@ -1,14 +1,27 @@
 pub mod error;
+pub mod section;
+
+/// Версия формата, начиная с которой за заголовком следует раздел метаданных,
+/// а файл завершается кодом аутентификации
+
+pub const SECTION_VERSION: u8 = 2;
 
 /// Записывается в начало шифрованного файла  42 байта.
 ///
 /// Уникальная метка для индентификации файла,	6 байт.
 /// Версия формата файла,	1 байт.
 /// Алгоритм шифрования, 1 байт.
-/// Исходный формат файла, 1 байт.
-/// Зарезервированные байты, 5 байт.
+/// Исходный формат файла, 2 байта (little-endian, в первой версии - 1 байт).
+/// Зарезервированные байты, 4 байта.
 /// Случайная соль, 16 байт.
 /// Вектор Инициализации, 12 байт.
+///
+/// Начиная со второй версии формата за ними следуют:
+/// Длина раздела метаданных, 4 байта.
+/// Зашифрованный раздел метаданных.
+///
+/// В конце такого файла, после зашифрованных данных и дополнения, записывается
+/// код аутентификации HMAC-SHA256 всего предшествующего содержимого, 32 байта.
 #[derive(Debug)]
 
 pub struct Scriber<RT, CA, SP, NP>
@@ -23,6 +36,7 @@ where
     version: u8,
     salt: SP,
     nonce: NP,
+    section: Vec<u8>,
 }
 
 impl<RT, CA, SP, NP> crate::abstraction::Header for Scriber<RT, CA, SP, NP>
@@ -51,9 +65,10 @@ where
         Self {
             format,
             cipher,
-            version: 1,
+            version: SECTION_VERSION,
             salt: salt,
             nonce: nonce,
+            section: Vec::new(),
         }
     }
 
@@ -74,11 +89,11 @@ where
 
         offset += 1;
 
-        buf[offset] = self.format.to_byte();
+        buf[offset..offset + 2].copy_from_slice(&self.format.to_code().to_le_bytes());
 
-        offset += 1;
+        offset += 2;
 
-        offset += 5;
+        offset += 4;
 
         buf[offset..offset + 16].copy_from_slice(self.salt.as_bytes());
 
@@ -117,12 +132,14 @@ where
 
         offset += 1;
 
-        let format =
-            Self::Format::from_byte(buf[offset]).map_err(|e| Self::Error::FormatError(e))?;
+        // Старшие байты кода формата были зарезервированы и равны нулю,
+        // поэтому однобайтовые коды первых версий читаются без изменений
+        let format = Self::Format::from_code(u16::from_le_bytes([buf[offset], buf[offset + 1]]))
+            .map_err(|e| Self::Error::FormatError(e))?;
 
-        offset += 1;
+        offset += 2;
 
-        offset += 5;
+        offset += 4;
 
         let salt_slice = &buf[offset..offset + SP::SALT_SIZE];
 
@@ -134,18 +151,55 @@ where
 
         let nonce = NP::from_bytes(nonce_slice).map_err(|e| Self::Error::NonceError(e.into()))?;
 
+        offset += NP::NONCE_SIZE;
+
+        let section = if version >= SECTION_VERSION {
+            let len = buf
+                .get(offset..offset + 4)
+                .ok_or(Self::Error::ExcessError)?;
+
+            let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+
+            offset += 4;
+
+            buf.get(offset..offset + len)
+                .ok_or(Self::Error::ExcessError)?
+                .to_vec()
+        } else {
+            Vec::new()
+        };
+
         Ok(Self {
             version: version,
             cipher: cipher,
             format: format,
             salt: salt,
             nonce: nonce,
+            section,
         })
     }
 
+    fn size(&self) -> usize {
+        if self.version >= SECTION_VERSION {
+            42 + 4 + self.section.len()
+        } else {
+            42
+        }
+    }
+
+    fn get_section(&self) -> &[u8] {
+        &self.section
+    }
+
+    fn set_section(&mut self, section: Vec<u8>) {
+        self.section = section;
+    }
+
     fn write_to_buffer(&mut self, old_buf: &mut [u8], new_buf: &mut [u8]) {
+        let header_len = self.size();
+
         // 1. Проверка размера буфера new_buf (критический шаг)
-        let required_len = 42 + old_buf.len();
+        let required_len = header_len + old_buf.len();
 
         // if new_buf.len() < required_len {
         //     return Err("Размер выходного буфера 'new_buf' недостаточен для Заголовка и Данных.");
@@ -156,29 +210,19 @@ where
         // 3. Копируем заголовок в начало new_buf
         new_buf[..42].copy_from_slice(&header_bytes);
 
+        if self.version >= SECTION_VERSION {
+            new_buf[42..46].copy_from_slice(&(self.section.len() as u32).to_le_bytes());
+
+            new_buf[46..header_len].copy_from_slice(&self.section);
+        }
+
         // 4. Копируем данные после заголовка
-        new_buf[42..required_len].copy_from_slice(old_buf);
+        new_buf[header_len..required_len].copy_from_slice(old_buf);
         // Обнуляем лишние байты, если new_buf был больше required_len
         // (Хотя лучше, чтобы new_buf был точно required_len)
         // Ok(())
     }
 
-    fn remove_from_buffer(&mut self, old_buf: &mut [u8], new_buf: &mut [u8]) {
-        // 1. Проверка минимального размера
-        // if old_buf.len() < HEADER_SIZE {
-        //     return Err("Входной буфер слишком мал, чтобы содержать заголовок.");
-        // }
-        // 2. Проверка размера выходного буфера
-        let data_len = old_buf.len() - 42;
-
-        // if new_buf.len() < data_len {
-        //     return Err("Размер выходного буфера 'new_buf' недостаточен для данных.");
-        // }
-        // 3. Пропускаем заголовок и копируем только данные
-        new_buf[..data_len].copy_from_slice(&old_buf[42..]);
-        // Ok(())
-    }
-
     fn get_format(&self) -> Self::Format {
         self.format.clone()
     }
@@ -194,4 +238,8 @@ where
     fn get_cipher(&self) -> Self::Cipher {
         self.cipher.clone()
     }
+
+    fn get_version(&self) -> u8 {
+        self.version
+    }
 }
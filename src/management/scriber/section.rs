@@ -0,0 +1,81 @@
+/// Раздел метаданных заголовка, хранится в файле в зашифрованном виде.
+///
+/// Последовательность записей: тег, 1 байт. Длина значения, 4 байта. Значение.
+
+#[derive(Debug, Default, Clone)]
+
+pub struct Section {
+    records: Vec<(u8, Vec<u8>)>,
+}
+
+/// Теги записей раздела метаданных
+
+pub mod tag {
+    /// Метаданные исходного ресурса: права, время, владелец, расширенные атрибуты
+    pub const METADATA: u8 = 1;
+    /// Исходное имя ресурса в UTF-8
+    pub const NAME: u8 = 2;
+    /// Алгоритм сжатия данных перед шифрованием, 1 байт
+    pub const COMPRESSION: u8 = 3;
+    /// Длина дополнения в конце зашифрованных данных, 8 байт
+    pub const PADDING: u8 = 4;
+    /// Схема дополнения, по которой файл дополняется заново при редактировании
+    pub const PADDING_SCHEME: u8 = 5;
+}
+
+impl Section {
+    /// Добавляет запись, заменяя прежнюю с тем же тегом
+
+    pub fn insert(&mut self, tag: u8, value: Vec<u8>) {
+        self.records.retain(|(t, _)| *t != tag);
+
+        self.records.push((tag, value));
+    }
+
+    pub fn remove(&mut self, tag: u8) {
+        self.records.retain(|(t, _)| *t != tag);
+    }
+
+    pub fn get(&self, tag: u8) -> Option<&[u8]> {
+        self.records
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, value)| value.as_slice())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        for (tag, value) in &self.records {
+            buf.push(*tag);
+
+            buf.extend_from_slice(&(value.len() as u32).to_le_bytes());
+
+            buf.extend_from_slice(value);
+        }
+
+        buf
+    }
+
+    /// Разбор раздела, `None` - раздел поврежден или расшифрован неверным паролем
+
+    pub fn from_bytes(mut bytes: &[u8]) -> Option<Self> {
+        let mut records = Vec::new();
+
+        while let [tag, rest @ ..] = bytes {
+            let (len, rest) = rest.split_first_chunk::<4>()?;
+
+            let len = u32::from_le_bytes(*len) as usize;
+
+            if rest.len() < len {
+                return None;
+            }
+
+            records.push((*tag, rest[..len].to_vec()));
+
+            bytes = &rest[len..];
+        }
+
+        Some(Section { records })
+    }
+}
@@ -0,0 +1,239 @@
+use super::error::Error;
+
+/// Длина встроенных данных, 4 байта little-endian перед ними.
+///
+/// Сигнатуры нет: младшие биты контейнера не должны выдавать наличие данных.
+
+const LENGTH_SIZE: usize = 4;
+
+/// Контейнер без потерь, в младшие биты которого встраиваются данные
+
+enum Carrier {
+    /// Изображение PNG: младшие биты цветовых каналов (яркости или R, G и B)
+    /// каждого пикселя, прозрачность не меняется
+    Png(image::DynamicImage),
+    /// Звук WAV в PCM: младший бит каждого отсчета
+    Wav(Wav),
+}
+
+/// Расположение отсчетов в файле WAV
+
+struct Wav {
+    bytes: Vec<u8>,
+    /// Начало и конец блока `data`
+    data: std::ops::Range<usize>,
+    /// Байт в одном отсчете, младший байт отсчета идет первым
+    sample: usize,
+}
+
+/// Встраивает данные в контейнер, возвращает файл контейнера того же формата
+
+pub fn embed(carrier: &[u8], data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut carrier = Carrier::parse(carrier)?;
+
+    let mut payload = Vec::with_capacity(LENGTH_SIZE + data.len());
+
+    payload.extend_from_slice(&(data.len() as u32).to_le_bytes());
+
+    payload.extend_from_slice(data);
+
+    let capacity = carrier.capacity();
+
+    if data.len() > u32::MAX as usize || payload.len() > capacity {
+        return Err(Error::CarrierTooSmall(payload.len(), capacity));
+    }
+
+    let bits = payload
+        .iter()
+        .flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1));
+
+    for (byte, bit) in carrier.bytes_mut().zip(bits) {
+        *byte = (*byte & !1) | bit;
+    }
+
+    carrier.to_bytes()
+}
+
+/// Извлекает данные, встроенные `embed`
+
+pub fn extract(carrier: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut carrier = Carrier::parse(carrier)?;
+
+    let capacity = carrier.capacity();
+
+    let mut bytes = carrier.bytes_mut().map(|byte| *byte & 1);
+
+    let mut next =
+        || -> Option<u8> { (0..8).try_fold(0u8, |byte, bit| Some(byte | bytes.next()? << bit)) };
+
+    let mut length = [0u8; LENGTH_SIZE];
+
+    for byte in &mut length {
+        *byte = next().ok_or(Error::NoHiddenData)?;
+    }
+
+    let length = u32::from_le_bytes(length) as usize;
+
+    if length > capacity - LENGTH_SIZE {
+        return Err(Error::NoHiddenData);
+    }
+
+    (0..length)
+        .map(|_| next().ok_or(Error::NoHiddenData))
+        .collect()
+}
+
+impl Carrier {
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            let color = Self::png_color(bytes).ok_or(Error::UnsupportedCarrier)?;
+
+            let picture = image::load_from_memory_with_format(bytes, image::ImageFormat::Png)
+                .map_err(Error::CarrierImage)?;
+
+            // Изображение записывается обратно в том же виде, поэтому подходят лишь
+            // те, что декодируются без преобразования (например, без блока tRNS)
+            if picture.color() != color {
+                return Err(Error::UnsupportedCarrier);
+            }
+
+            return Ok(Self::Png(picture));
+        }
+
+        if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
+            return Wav::parse(bytes).map(Self::Wav);
+        }
+
+        Err(Error::UnsupportedCarrier)
+    }
+
+    /// Цветовой тип PNG по заголовку IHDR.
+    ///
+    /// Подходят 8 бит на канал без палитры: палитра и глубина 1, 2, 4 и 16 бит
+    /// при декодировании расширяются до другого представления и не пережили бы запись.
+
+    fn png_color(bytes: &[u8]) -> Option<image::ColorType> {
+        // Сигнатура, длина и тип блока, ширина и высота
+        const DEPTH: usize = 8 + 8 + 8;
+
+        if bytes.get(12..16)? != b"IHDR" || *bytes.get(DEPTH)? != 8 {
+            return None;
+        }
+
+        match bytes.get(DEPTH + 1)? {
+            0 => Some(image::ColorType::L8),
+            2 => Some(image::ColorType::Rgb8),
+            4 => Some(image::ColorType::La8),
+            6 => Some(image::ColorType::Rgba8),
+            _ => None,
+        }
+    }
+
+    /// Сколько байт помещается в контейнер вместе с длиной
+
+    fn capacity(&mut self) -> usize {
+        self.bytes_mut().count() / 8
+    }
+
+    /// Байты, младшие биты которых несут данные
+
+    fn bytes_mut(&mut self) -> Box<dyn Iterator<Item = &mut u8> + '_> {
+        match self {
+            Self::Png(image::DynamicImage::ImageLuma8(picture)) => Box::new(picture.iter_mut()),
+            Self::Png(image::DynamicImage::ImageLumaA8(picture)) => {
+                Box::new(picture.iter_mut().step_by(2))
+            }
+            Self::Png(image::DynamicImage::ImageRgb8(picture)) => Box::new(picture.iter_mut()),
+            Self::Png(image::DynamicImage::ImageRgba8(picture)) => Box::new(
+                picture
+                    .chunks_exact_mut(4)
+                    .flat_map(|pixel| pixel.iter_mut().take(3)),
+            ),
+            // Другие представления отклоняются в `parse`
+            Self::Png(_) => Box::new(std::iter::empty()),
+            Self::Wav(wav) => Box::new(wav.bytes[wav.data.clone()].iter_mut().step_by(wav.sample)),
+        }
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Png(picture) => {
+                let mut bytes = std::io::Cursor::new(Vec::new());
+
+                picture
+                    .write_to(&mut bytes, image::ImageFormat::Png)
+                    .map_err(Error::CarrierImage)?;
+
+                Ok(bytes.into_inner())
+            }
+            Self::Wav(wav) => Ok(wav.bytes.clone()),
+        }
+    }
+}
+
+impl Wav {
+    /// Разбор блоков RIFF: нужны `fmt ` с целочисленным PCM и `data`
+
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let mut offset = 12;
+
+        let mut sample = None;
+
+        while let Some(header) = bytes.get(offset..offset + 8) {
+            let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+            let start = offset + 8;
+
+            let end = start.saturating_add(size).min(bytes.len());
+
+            match &header[..4] {
+                b"fmt " => sample = Some(Self::sample(&bytes[start..end])?),
+                b"data" => {
+                    let sample = sample.ok_or(Error::UnsupportedCarrier)?;
+
+                    // Неполный последний отсчет не используется
+                    let end = start + (end - start) / sample * sample;
+
+                    return Ok(Self {
+                        bytes: bytes.to_vec(),
+                        data: start..end,
+                        sample,
+                    });
+                }
+                _ => {}
+            }
+
+            // Блоки выравниваются по двум байтам
+            offset = start.saturating_add(size).saturating_add(size & 1);
+        }
+
+        Err(Error::UnsupportedCarrier)
+    }
+
+    /// Размер отсчета по блоку `fmt `, числа с плавающей точкой и сжатие не подходят
+
+    fn sample(fmt: &[u8]) -> Result<usize, Error> {
+        const PCM: u16 = 1;
+
+        const EXTENSIBLE: u16 = 0xFFFE;
+
+        let field = |at: usize| {
+            fmt.get(at..at + 2)
+                .map(|field| u16::from_le_bytes([field[0], field[1]]))
+        };
+
+        let format = match field(0).ok_or(Error::UnsupportedCarrier)? {
+            // Формат WAVE_FORMAT_EXTENSIBLE уточняется первыми байтами GUID
+            EXTENSIBLE => field(24).ok_or(Error::UnsupportedCarrier)?,
+            format => format,
+        };
+
+        let bits = field(14).ok_or(Error::UnsupportedCarrier)?;
+
+        if format != PCM || bits == 0 || !bits.is_multiple_of(8) || bits > 32 {
+            return Err(Error::UnsupportedCarrier);
+        }
+
+        Ok(bits as usize / 8)
+    }
+}
@@ -0,0 +1,127 @@
+use sha2::Digest;
+
+/// Сигнатура тома разделенного файла
+
+const MAGIC: &[u8; 8] = b"DEENVOL\0";
+
+/// Версия заголовка тома
+
+const VERSION: u8 = 1;
+
+/// Размер заголовка тома: сигнатура, версия, идентификатор набора,
+/// номер тома, число томов и SHA-256 содержимого тома
+
+pub const HEADER_SIZE: usize = MAGIC.len() + 1 + 16 + 4 + 4 + 32;
+
+/// Заголовок тома
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+
+pub struct Volume {
+    /// Случайный идентификатор, общий для всех томов одного файла
+    pub set: [u8; 16],
+    /// Номер тома, начиная с 1
+    pub index: u32,
+    pub total: u32,
+}
+
+/// Делит данные на тома размером не больше `size` байт вместе с заголовком
+
+pub fn split(data: &[u8], size: usize) -> Vec<Vec<u8>> {
+    let chunk = size.saturating_sub(HEADER_SIZE).max(1);
+
+    let set = rand::random::<[u8; 16]>();
+
+    let chunks: Vec<&[u8]> = if data.is_empty() {
+        vec![data]
+    } else {
+        data.chunks(chunk).collect()
+    };
+
+    let total = chunks.len() as u32;
+
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut volume = Vec::with_capacity(HEADER_SIZE + chunk.len());
+
+            volume.extend_from_slice(MAGIC);
+
+            volume.push(VERSION);
+
+            volume.extend_from_slice(&set);
+
+            volume.extend_from_slice(&(index as u32 + 1).to_le_bytes());
+
+            volume.extend_from_slice(&total.to_le_bytes());
+
+            volume.extend_from_slice(&sha2::Sha256::digest(chunk));
+
+            volume.extend_from_slice(chunk);
+
+            volume
+        })
+        .collect()
+}
+
+/// Начинаются ли данные с заголовка тома
+
+pub fn is_volume(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Разбирает заголовок тома и проверяет контрольную сумму содержимого.
+///
+/// `None` - заголовок не распознан. Последнее значение - совпала ли контрольная
+/// сумма: поврежденное содержимое еще может восстановить слой избыточности.
+
+pub fn parse(bytes: &[u8]) -> Option<(Volume, &[u8], bool)> {
+    if bytes.len() < HEADER_SIZE || !is_volume(bytes) || bytes[MAGIC.len()] != VERSION {
+        return None;
+    }
+
+    let (header, payload) = bytes.split_at(HEADER_SIZE);
+
+    let mut fields = &header[MAGIC.len() + 1..];
+
+    let mut take = |len: usize| {
+        let (field, rest) = fields.split_at(len);
+
+        fields = rest;
+
+        field
+    };
+
+    let set = take(16).try_into().ok()?;
+
+    let index = u32::from_le_bytes(take(4).try_into().ok()?);
+
+    let total = u32::from_le_bytes(take(4).try_into().ok()?);
+
+    let digest = take(32);
+
+    if index == 0 || index > total {
+        return None;
+    }
+
+    let intact = *digest == *sha2::Sha256::digest(payload);
+
+    Some((Volume { set, index, total }, payload, intact))
+}
+
+/// Имя тома: исходное имя и номер из трех и более цифр
+
+pub fn name(base: &str, index: u32, total: u32) -> String {
+    let width = total.to_string().len().max(3);
+
+    format!("{}.{:0width$}", base, index, width = width)
+}
+
+/// Исходное имя по имени тома: `archive.tar.002` -> `archive.tar`
+
+pub fn base(name: &str) -> Option<&str> {
+    let (base, number) = name.rsplit_once('.')?;
+
+    (number.len() >= 3 && number.bytes().all(|b| b.is_ascii_digit())).then_some(base)
+}
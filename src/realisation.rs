@@ -1,3 +1,5 @@
+pub mod compression;
 pub mod derive_key;
 pub mod encryption;
 pub mod object;
+pub mod padding;
@@ -0,0 +1,95 @@
+use std::io::{Read, Write};
+
+pub mod error;
+
+/// Уровень сжатия zstd, компромисс между скоростью и размером
+
+const ZSTD_LEVEL: i32 = 3;
+
+/// Алгоритмы сжатия данных перед шифрованием
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+
+pub enum CompressionFormat {
+    Zstd,
+    Deflate,
+    None,
+}
+
+impl core::fmt::Display for CompressionFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompressionFormat::Zstd => write!(f, "zstd"),
+            CompressionFormat::Deflate => write!(f, "deflate"),
+            CompressionFormat::None => write!(f, "none"),
+        }
+    }
+}
+
+impl core::str::FromStr for CompressionFormat {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "zstd" => Ok(CompressionFormat::Zstd),
+            "deflate" => Ok(CompressionFormat::Deflate),
+            "none" => Ok(CompressionFormat::None),
+            _ => Err("Invalid compression format. Use 'zstd', 'deflate' or 'none'."),
+        }
+    }
+}
+
+impl crate::abstraction::CompressionList for CompressionFormat {
+    type Error = error::Error;
+
+    fn compress(&self, buf: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        match self {
+            CompressionFormat::Zstd => {
+                zstd::encode_all(buf, ZSTD_LEVEL).map_err(error::Error::CompressError)
+            }
+            CompressionFormat::Deflate => {
+                let mut encoder =
+                    flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+
+                encoder
+                    .write_all(buf)
+                    .map_err(error::Error::CompressError)?;
+
+                encoder.finish().map_err(error::Error::CompressError)
+            }
+            CompressionFormat::None => Ok(buf.to_vec()),
+        }
+    }
+
+    fn decompress(&self, buf: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        match self {
+            CompressionFormat::Zstd => zstd::decode_all(buf).map_err(error::Error::DecompressError),
+            CompressionFormat::Deflate => {
+                let mut result = Vec::new();
+
+                flate2::read::DeflateDecoder::new(buf)
+                    .read_to_end(&mut result)
+                    .map_err(error::Error::DecompressError)?;
+
+                Ok(result)
+            }
+            CompressionFormat::None => Ok(buf.to_vec()),
+        }
+    }
+
+    fn to_byte(&self) -> u8 {
+        match self {
+            CompressionFormat::None => 0,
+            CompressionFormat::Zstd => 1,
+            CompressionFormat::Deflate => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, Self::Error> {
+        match byte {
+            0 => Ok(CompressionFormat::None),
+            1 => Ok(CompressionFormat::Zstd),
+            2 => Ok(CompressionFormat::Deflate),
+            _ => Err(Self::Error::BrokenByteCompression),
+        }
+    }
+}
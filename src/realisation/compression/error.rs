@@ -0,0 +1,29 @@
+#[derive(Debug)]
+
+pub enum Error {
+    BrokenByteCompression,
+    CompressError(std::io::Error),
+    DecompressError(std::io::Error),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BrokenByteCompression => {
+                write!(f, "Ошибка подписи байта алгоритма сжатия")
+            }
+            Self::CompressError(e) => write!(f, "Ошибка сжатия данных: {}", e),
+            Self::DecompressError(e) => write!(f, "Ошибка распаковки данных: {}", e),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::BrokenByteCompression => None,
+            Self::CompressError(e) => Some(e),
+            Self::DecompressError(e) => Some(e),
+        }
+    }
+}
@@ -91,4 +91,22 @@ impl Encryption for EncryptionRealisation {
             EncryptionRealisation::XORRealisation(e) => e.encode(buf),
         }
     }
+
+    fn seekable(&self) -> bool {
+        match self {
+            EncryptionRealisation::XORRealisation(e) => e.seekable(),
+        }
+    }
+
+    fn encode_at(&self, buf: &mut [u8], offset: usize) {
+        match self {
+            EncryptionRealisation::XORRealisation(e) => e.encode_at(buf, offset),
+        }
+    }
+
+    fn decode_at(&self, buf: &mut [u8], offset: usize) {
+        match self {
+            EncryptionRealisation::XORRealisation(e) => e.decode_at(buf, offset),
+        }
+    }
 }
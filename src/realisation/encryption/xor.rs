@@ -14,6 +14,18 @@ impl Encryption for XorEncryption {
     }
 
     fn encode(&self, buf: &mut [u8]) {
+        self.encode_at(buf, 0);
+    }
+
+    fn decode(&self, buf: &mut [u8]) {
+        self.decode_at(buf, 0);
+    }
+
+    fn seekable(&self) -> bool {
+        true
+    }
+
+    fn encode_at(&self, buf: &mut [u8], offset: usize) {
         let key_len = self.key.len();
 
         if key_len == 0 {
@@ -21,13 +33,13 @@ impl Encryption for XorEncryption {
         }
 
         for (i, byte) in buf.iter_mut().enumerate() {
-            let key_byte = self.key[i % key_len];
+            let key_byte = self.key[(offset + i) % key_len];
 
             *byte ^= key_byte;
         }
     }
 
-    fn decode(&self, buf: &mut [u8]) {
+    fn decode_at(&self, buf: &mut [u8], offset: usize) {
         let key_len = self.key.len();
 
         if key_len == 0 {
@@ -35,7 +47,7 @@ impl Encryption for XorEncryption {
         }
 
         for (i, byte) in buf.iter_mut().enumerate() {
-            let key_byte = self.key[i % key_len];
+            let key_byte = self.key[(offset + i) % key_len];
 
             *byte ^= key_byte;
         }
@@ -1,23 +1,95 @@
 use crate::abstraction::ResourcePath;
-use std::io::{Read, Write};
+use std::io::{Read, Seek, Write};
 
 pub mod path;
 pub mod resource_type;
 
+/// Объем данных, начиная с которого обычные файлы отображаются в память
+
+const MMAP_THRESHOLD: usize = 1 << 20;
+
+/// Файл на диске.
+///
+/// Большие обычные файлы читаются и пишутся через отображение в память:
+/// `read_to_end` отдает отображенные страницы, а `write_with` заполняет их
+/// в выходном файле. Каналы и специальные файлы читаются и пишутся буферизованно.
 #[derive(Debug)]
 
 pub struct FileResourceIdentifier {
     file: std::fs::File,
     path: path::file_system::FilePath,
+    map: Option<memmap2::Mmap>,
+    regular: bool,
+    position: usize,
+}
+
+impl FileResourceIdentifier {
+    fn from_file(file: std::fs::File, path: path::file_system::FilePath) -> std::io::Result<Self> {
+        let metadata = file.metadata()?;
+
+        let regular = metadata.file_type().is_file();
+
+        // SAFETY: файл открыт только на чтение, отображение живет не дольше
+        // дескриптора, изменение файла другим процессом во время шифрования
+        // не поддерживается так же, как и при буферизованном чтении.
+        let map = if regular && metadata.len() as usize >= MMAP_THRESHOLD {
+            Some(unsafe { memmap2::Mmap::map(&file)? })
+        } else {
+            None
+        };
+
+        Ok(FileResourceIdentifier {
+            file,
+            path,
+            map,
+            regular,
+            position: 0,
+        })
+    }
+
+    /// Отображает в память `len` байт файла с текущей позиции, расширяя файл до нужного размера
+
+    fn map_mut(&mut self, len: usize) -> std::io::Result<memmap2::MmapMut> {
+        self.file.set_len((self.position + len) as u64)?;
+
+        // SAFETY: файл открыт на чтение и запись этим же процессом,
+        // отображение сбрасывается на диск и освобождается вызывающим кодом.
+        unsafe {
+            memmap2::MmapOptions::new()
+                .offset(self.position as u64)
+                .len(len)
+                .map_mut(&self.file)
+        }
+    }
 }
 
 impl crate::abstraction::Reader for FileResourceIdentifier {
     type Error = std::io::Error;
 
-    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
-        self.file.read_exact(buf)?;
+    fn read_to_end(&mut self) -> Result<crate::abstraction::Data, Self::Error> {
+        // Отображение целиком отдается вызывающему коду без копирования в кучу
+        if let (Some(map), 0) = (&self.map, self.position) {
+            self.position = map.len();
 
-        Ok(buf.len())
+            let map = self.map.take().expect("отображение проверено выше");
+
+            return Ok(crate::abstraction::Data::Mapped(Box::new(map)));
+        }
+
+        // Размер обычного файла известен заранее, у каналов и устройств он нулевой
+        let mut data = Vec::with_capacity(self.path.size().saturating_sub(self.position));
+
+        match &self.map {
+            Some(map) => data.extend_from_slice(&map[self.position.min(map.len())..]),
+            // Размер каналов и устройств неизвестен, они читаются до конца потока
+            None => {
+                self.file.read_to_end(&mut data)?;
+            }
+        }
+
+        self.position += data.len();
+
+        Ok(data.into())
     }
 }
 
@@ -27,8 +99,40 @@ impl crate::abstraction::Writer for FileResourceIdentifier {
     fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
         self.file.write_all(buf)?;
 
+        self.position += buf.len();
+
         Ok(buf.len())
     }
+
+    fn write_with(
+        &mut self,
+        len: usize,
+        fill: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<usize, Self::Error> {
+        if !self.regular || len < MMAP_THRESHOLD {
+            let mut buf = vec![0u8; len];
+
+            fill(&mut buf);
+
+            return self.write(&buf);
+        }
+
+        // Данные собираются и шифруются прямо в страницах выходного файла
+        let mut map = self.map_mut(len)?;
+
+        fill(&mut map);
+
+        map.flush()?;
+
+        drop(map);
+
+        self.position += len;
+
+        self.file
+            .seek(std::io::SeekFrom::Start(self.position as u64))?;
+
+        Ok(len)
+    }
 }
 
 impl crate::abstraction::UnifiedResourceIdentifierAbstraction for FileResourceIdentifier {
@@ -49,34 +153,33 @@ impl crate::abstraction::UnifiedResourceIdentifierAbstraction for FileResourceId
             crate::abstraction::Operation::Open => {
                 let file = std::fs::File::open(&path.get_path())?;
 
-                Ok(FileResourceIdentifier { file, path: path })
+                FileResourceIdentifier::from_file(file, path)
             }
             crate::abstraction::Operation::Create => {
-                let file = std::fs::File::create(&path.get_path())?;
+                // Чтение нужно только для отображения обычного файла в память,
+                // каналы и устройства открываются лишь на запись
+                let regular = std::fs::metadata(path.get_path())
+                    .map(|metadata| metadata.is_file())
+                    .unwrap_or(true);
 
-                Ok(FileResourceIdentifier { file, path: path })
+                let file = std::fs::OpenOptions::new()
+                    .read(regular)
+                    .write(true)
+                    .create(true)
+                    .truncate(regular)
+                    .open(&path.get_path())?;
+
+                FileResourceIdentifier::from_file(file, path)
             }
             crate::abstraction::Operation::Truncate => {
                 let file = std::fs::OpenOptions::new()
+                    .read(true)
                     .write(true)
                     .truncate(true)
                     .open(&path.get_path())?;
 
-                Ok(FileResourceIdentifier { file, path: path })
+                FileResourceIdentifier::from_file(file, path)
             }
         }
     }
-
-    fn path(&mut self) -> &mut Self::Path {
-        &mut self.path
-    }
-
-    fn type_resource(
-        &mut self,
-    ) -> Result<Self::Type, <Self::Type as crate::abstraction::ResourceTypeList>::Error> {
-        match file_format::FileFormat::from_reader(&mut self.file) {
-            Ok(r) => Ok(Self::Type::FileFormat(r)),
-            Err(e) => Err(crate::realisation::object::file::resource_type::error::Error::ReadError),
-        }
-    }
 }
@@ -1 +1,2 @@
 pub mod file_system;
+pub mod metadata;
@@ -15,7 +15,9 @@ impl crate::abstraction::ResourcePath for FilePath {
 
         match std::fs::metadata(&path) {
             Ok(metadata) => {
-                if metadata.is_file() {
+                // Каналы и специальные файлы допустимы, они обрабатываются
+                // буферизованным вводом-выводом
+                if !metadata.is_dir() {
                     Ok(FilePath(path))
                 } else {
                     Err(std::io::Error::new(
@@ -50,4 +52,70 @@ impl crate::abstraction::ResourcePath for FilePath {
     fn get_path(&self) -> &Self::Path {
         &self.0
     }
+
+    fn metadata(&self) -> Result<Vec<u8>, Self::Error> {
+        Ok(super::metadata::FileMetadata::read(&self.0)?.to_bytes())
+    }
+
+    fn set_metadata(&self, metadata: &[u8]) -> Result<(), Self::Error> {
+        // Права и время каналов и устройств не трогаем
+        if !std::fs::metadata(&self.0)?.is_file() {
+            return Ok(());
+        }
+
+        super::metadata::FileMetadata::from_bytes(metadata)?.apply(&self.0)
+    }
+
+    fn name(&self) -> String {
+        self.0
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn same_resource(&self, other: &Self) -> bool {
+        match (std::fs::metadata(&self.0), std::fs::metadata(&other.0)) {
+            #[cfg(unix)]
+            (Ok(a), Ok(b)) => {
+                use std::os::unix::fs::MetadataExt;
+
+                a.dev() == b.dev() && a.ino() == b.ino()
+            }
+            _ => match (self.0.canonicalize(), other.0.canonicalize()) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => self.0 == other.0,
+            },
+        }
+    }
+
+    fn check_rename(&self, name: &str) -> Result<(), Self::Error> {
+        // Имя приходит из расшифрованного заголовка, выход за пределы каталога недопустим
+        if std::path::Path::new(name).file_name() != Some(std::ffi::OsStr::new(name)) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Недопустимое имя файла '{}'", name),
+            ));
+        }
+
+        let path = self.0.with_file_name(name);
+
+        if path.exists() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("Файл '{}' уже существует", path.display()),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn rename(&self, name: &str) -> Result<Self, Self::Error> {
+        self.check_rename(name)?;
+
+        let path = self.0.with_file_name(name);
+
+        std::fs::rename(&self.0, &path)?;
+
+        Ok(FilePath(path))
+    }
 }
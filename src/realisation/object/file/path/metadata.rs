@@ -0,0 +1,247 @@
+/// Метаданные файла, сохраняемые в зашифрованном заголовке.
+///
+/// Права доступа, время доступа и изменения, владелец и расширенные атрибуты.
+#[derive(Debug, Default, Clone)]
+
+pub struct FileMetadata {
+    mode: u32,
+    uid: u32,
+    gid: u32,
+    accessed: std::time::Duration,
+    modified: std::time::Duration,
+    xattrs: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl FileMetadata {
+    /// Считывает метаданные файла по пути
+
+    pub fn read(path: &std::path::Path) -> std::io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+
+        let since_epoch = |time: std::io::Result<std::time::SystemTime>| {
+            time.ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .unwrap_or_default()
+        };
+
+        let mut result = FileMetadata {
+            accessed: since_epoch(metadata.accessed()),
+            modified: since_epoch(metadata.modified()),
+            ..Default::default()
+        };
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            result.mode = metadata.mode() & 0o7777;
+
+            result.uid = metadata.uid();
+
+            result.gid = metadata.gid();
+        }
+
+        #[cfg(not(unix))]
+        {
+            result.mode = if metadata.permissions().readonly() {
+                0o444
+            } else {
+                0o644
+            };
+        }
+
+        // Файловая система может не поддерживать расширенные атрибуты
+        #[cfg(unix)]
+        if let Ok(names) = xattr::list(path) {
+            use std::os::unix::ffi::OsStrExt;
+
+            for name in names {
+                if let Ok(Some(value)) = xattr::get(path, &name) {
+                    result.xattrs.push((name.as_bytes().to_vec(), value));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Восстанавливает метаданные файла по пути.
+    ///
+    /// Смена владельца без прав суперпользователя и расширенные атрибуты, которые
+    /// файловая система не принимает, пропускаются, как это делают `cp -p` и `tar`.
+
+    pub fn apply(&self, path: &std::path::Path) -> std::io::Result<()> {
+        // Дескриптор открывается до смены прав, иначе файл только для чтения
+        // не удастся открыть для установки времени
+        let file = std::fs::File::options().write(true).open(path)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            use std::os::unix::fs::PermissionsExt;
+
+            // Владелец, которого нет в пространстве имен пользователей, дает EINVAL
+            match std::os::unix::fs::chown(path, Some(self.uid), Some(self.gid)) {
+                Err(e) if skippable(&e) => {}
+                result => result?,
+            }
+
+            // Файловая система назначения может не поддерживать атрибуты или их
+            // пространство имен: атрибут пропускается, остальное восстанавливается
+            for (name, value) in &self.xattrs {
+                let name = std::ffi::OsStr::from_bytes(name);
+
+                match xattr::set(path, name, value) {
+                    Err(e) if skippable(&e) => eprintln!(
+                        "Расширенный атрибут {} не восстановлен: {}",
+                        name.to_string_lossy(),
+                        e
+                    ),
+                    result => result?,
+                }
+            }
+
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(self.mode))?;
+        }
+
+        #[cfg(not(unix))]
+        {
+            let mut permissions = std::fs::metadata(path)?.permissions();
+
+            permissions.set_readonly(self.mode & 0o222 == 0);
+
+            std::fs::set_permissions(path, permissions)?;
+        }
+
+        let mut times = std::fs::FileTimes::new();
+
+        if let Some(accessed) = std::time::UNIX_EPOCH.checked_add(self.accessed) {
+            times = times.set_accessed(accessed);
+        }
+
+        if let Some(modified) = std::time::UNIX_EPOCH.checked_add(self.modified) {
+            times = times.set_modified(modified);
+        }
+
+        // Время выставляется последним, так как запись атрибутов его меняет
+        file.set_times(times)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        buf.extend_from_slice(&self.mode.to_le_bytes());
+
+        buf.extend_from_slice(&self.uid.to_le_bytes());
+
+        buf.extend_from_slice(&self.gid.to_le_bytes());
+
+        for time in [self.accessed, self.modified] {
+            buf.extend_from_slice(&time.as_secs().to_le_bytes());
+
+            buf.extend_from_slice(&time.subsec_nanos().to_le_bytes());
+        }
+
+        buf.extend_from_slice(&(self.xattrs.len() as u32).to_le_bytes());
+
+        for (name, value) in &self.xattrs {
+            for part in [name, value] {
+                buf.extend_from_slice(&(part.len() as u32).to_le_bytes());
+
+                buf.extend_from_slice(part);
+            }
+        }
+
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> std::io::Result<Self> {
+        let mut reader = Cursor(bytes);
+
+        let mode = reader.u32()?;
+
+        let uid = reader.u32()?;
+
+        let gid = reader.u32()?;
+
+        let accessed = std::time::Duration::new(reader.u64()?, reader.u32()?);
+
+        let modified = std::time::Duration::new(reader.u64()?, reader.u32()?);
+
+        let count = reader.u32()?;
+
+        let mut xattrs = Vec::new();
+
+        for _ in 0..count {
+            let name = reader.chunk()?;
+
+            let value = reader.chunk()?;
+
+            xattrs.push((name, value));
+        }
+
+        Ok(FileMetadata {
+            mode,
+            uid,
+            gid,
+            accessed,
+            modified,
+            xattrs,
+        })
+    }
+}
+
+/// Последовательное чтение полей из сериализованных метаданных
+
+struct Cursor<'a>(&'a [u8]);
+
+impl Cursor<'_> {
+    fn take(&mut self, len: usize) -> std::io::Result<&[u8]> {
+        if self.0.len() < len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Метаданные файла повреждены",
+            ));
+        }
+
+        let (head, tail) = self.0.split_at(len);
+
+        self.0 = tail;
+
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> std::io::Result<u32> {
+        let mut arr = [0u8; 4];
+
+        arr.copy_from_slice(self.take(4)?);
+
+        Ok(u32::from_le_bytes(arr))
+    }
+
+    fn u64(&mut self) -> std::io::Result<u64> {
+        let mut arr = [0u8; 8];
+
+        arr.copy_from_slice(self.take(8)?);
+
+        Ok(u64::from_le_bytes(arr))
+    }
+
+    fn chunk(&mut self) -> std::io::Result<Vec<u8>> {
+        let len = self.u32()? as usize;
+
+        Ok(self.take(len)?.to_vec())
+    }
+}
+
+/// Ошибки восстановления, из-за которых не стоит прерывать запись файла:
+/// нет прав, операция не поддерживается (ENOTSUP) или значение недопустимо (EINVAL)
+
+#[cfg(unix)]
+fn skippable(e: &std::io::Error) -> bool {
+    matches!(
+        e.kind(),
+        std::io::ErrorKind::PermissionDenied
+            | std::io::ErrorKind::Unsupported
+            | std::io::ErrorKind::InvalidInput
+    )
+}
@@ -1,88 +1,150 @@
+pub mod archive;
+pub mod code;
 pub mod common;
+pub mod doc;
 pub mod error;
+pub mod hex;
+pub mod odt;
+pub mod picture;
+pub mod render;
+pub mod rtf;
+pub mod structured;
+pub mod table;
+pub mod xml;
 
 #[derive(Debug, Clone)]
 
 pub enum ResourceType {
     FileFormat(file_format::FileFormat),
+    Csv,
+    Tsv,
+    Json,
+    Yaml,
+    Toml,
     Crypted,
     UnknowFormat,
 }
 
+/// Текстовые форматы, которые `file_format` определяет как `PlainText`:
+/// распознаются по расширению имени или задаются через `--format`
+
+const TEXT_FORMATS: &[(&str, ResourceType)] = &[
+    ("csv", ResourceType::Csv),
+    ("tsv", ResourceType::Tsv),
+    ("json", ResourceType::Json),
+    ("yaml", ResourceType::Yaml),
+    ("yml", ResourceType::Yaml),
+    ("toml", ResourceType::Toml),
+];
+
+impl ResourceType {
+    /// Формат, определенный `file_format`.
+    ///
+    /// Формат без кода в таблице `code::TABLE` не пережил бы запись в заголовок,
+    /// поэтому сразу считается неизвестным, а не подменяется при записи.
+
+    fn from_format(format: file_format::FileFormat) -> Self {
+        match code::to_code(format) {
+            Some(_) => Self::FileFormat(format),
+            None => Self::UnknowFormat,
+        }
+    }
+}
+
 impl crate::abstraction::ResourceTypeList for ResourceType {
     type Error = error::Error;
 
-    fn print_function(&self, bytes: &[u8]) {
-        match self {
-            Self::FileFormat(f) => {
-                match f {
-                    file_format::FileFormat::PlainText => {
-                        let text: std::borrow::Cow<str> = String::from_utf8_lossy(bytes);
-
-                        println!("--- Дешифрованный ТЕКСТ (TXT) ---");
-
-                        println!("{}", text);
+    fn renderers() -> crate::abstraction::Renderers<Self> {
+        render::renderers()
+    }
 
-                        println!("----------------------------------");
-                    }
-                    file_format::FileFormat::OfficeOpenXmlDocument => {
-                        println!("--- Дешифрованный DOCX ---");
+    fn detect(bytes: &[u8], name: &str) -> Self {
+        let format = file_format::FileFormat::from_bytes(bytes);
 
-                        // Используем docx-rs для чтения из байтов
-                        match docx_rs::read_docx(bytes) {
-                            Ok(docx) => {
-                                let text = common::extract_text_from_docx(&docx);
+        if format != file_format::FileFormat::PlainText {
+            return Self::from_format(format);
+        }
 
-                                println!("{}", text);
-                            }
-                            Err(e) => {
-                                println!("Ошибка парсинга DOCX файла: {:?}", e);
-                            }
-                        }
+        let extension = std::path::Path::new(name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
 
-                        println!("----------------------------");
-                    }
-                    _ => {
-                        println!("--- Дешифрованные БИНАРНЫЕ ДАННЫЕ (Формат: {:?}) ---", f);
+        if let Some((_, text)) = TEXT_FORMATS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(extension))
+        {
+            return text.clone();
+        }
 
-                        let slice_len = std::cmp::min(bytes.len(), 128);
+        // XML без объявления `<?xml ...?>` по содержимому не отличается от текста
+        if extension.eq_ignore_ascii_case("xml") {
+            return Self::FileFormat(file_format::FileFormat::ExtensibleMarkupLanguage);
+        }
 
-                        println!("Первые {} байт: {:?}", slice_len, &bytes[..slice_len]);
+        if structured::is_json(bytes) {
+            return Self::Json;
+        }
 
-                        println!("--------------------------------------------------");
-                    }
-                }
-            }
-            _ => {
-                println!("--- Дешифрованные БИНАРНЫЕ ДАННЫЕ (Неизвестный тип) ---");
+        Self::FileFormat(format)
+    }
 
-                let slice_len = std::cmp::min(bytes.len(), 128);
+    fn from_name(name: &str) -> Result<Self, Self::Error> {
+        if let Some((_, text)) = TEXT_FORMATS
+            .iter()
+            .find(|(text, _)| text.eq_ignore_ascii_case(name))
+        {
+            return Ok(text.clone());
+        }
 
-                println!("Первые {} байт: {:?}", slice_len, &bytes[..slice_len]);
+        code::find(name)
+            .map(Self::FileFormat)
+            .ok_or(Self::Error::UnknowFormat)
+    }
 
-                println!("--------------------------------------------------");
-            }
+    fn compressible(&self) -> bool {
+        match self {
+            Self::FileFormat(f) => !matches!(
+                f.kind(),
+                file_format::Kind::Archive
+                    | file_format::Kind::Compressed
+                    | file_format::Kind::Image
+                    | file_format::Kind::Audio
+                    | file_format::Kind::Video
+                    | file_format::Kind::Package
+            ),
+            Self::Csv | Self::Tsv | Self::Json | Self::Yaml | Self::Toml => true,
+            Self::Crypted => false,
+            Self::UnknowFormat => true,
         }
     }
 
-    fn to_byte(&self) -> u8 {
+    fn to_code(&self) -> u16 {
         match self {
-            ResourceType::FileFormat(file_format::FileFormat::OfficeOpenXmlDocument) => 3,
-            ResourceType::FileFormat(file_format::FileFormat::PlainText) => 2,
-            ResourceType::Crypted => 1,
-            _ => 0,
+            // Форматы без кода создаются как `UnknowFormat` в `from_format`
+            ResourceType::FileFormat(f) => code::to_code(*f).unwrap_or(code::UNKNOWN),
+            ResourceType::Csv => code::CSV,
+            ResourceType::Tsv => code::TSV,
+            ResourceType::Json => code::JSON,
+            ResourceType::Yaml => code::YAML,
+            ResourceType::Toml => code::TOML,
+            ResourceType::Crypted => code::CRYPTED,
+            ResourceType::UnknowFormat => code::UNKNOWN,
         }
     }
 
-    fn from_byte(byte: u8) -> Result<Self, Self::Error> {
-        match byte {
-            3 => Ok(ResourceType::FileFormat(
-                file_format::FileFormat::OfficeOpenXmlDocument,
-            )),
-            2 => Ok(ResourceType::FileFormat(file_format::FileFormat::PlainText)),
-            1 => Ok(ResourceType::Crypted),
-            0 => Ok(ResourceType::UnknowFormat),
-            _ => Err(Self::Error::BrokenByteFormat),
+    fn from_code(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            code::UNKNOWN => Ok(ResourceType::UnknowFormat),
+            code::CRYPTED => Ok(ResourceType::Crypted),
+            code::CSV => Ok(ResourceType::Csv),
+            code::TSV => Ok(ResourceType::Tsv),
+            code::JSON => Ok(ResourceType::Json),
+            code::YAML => Ok(ResourceType::Yaml),
+            code::TOML => Ok(ResourceType::Toml),
+            _ => code::from_code(value)
+                .map(ResourceType::FileFormat)
+                .ok_or(Self::Error::BrokenByteFormat),
         }
     }
 }
@@ -0,0 +1,212 @@
+use std::io::Read;
+
+/// Поддерживаемые форматы архивов
+
+#[derive(Debug, Clone, Copy)]
+
+pub enum Kind {
+    Zip,
+    Tar,
+    TarGz,
+    SevenZip,
+}
+
+/// Запись архива для оглавления
+
+pub struct Entry {
+    pub name: String,
+    pub size: u64,
+    pub modified: Option<String>,
+    pub directory: bool,
+}
+
+/// Проверяет по первому блоку, что gzip содержит TAR, не распаковывая его целиком
+
+pub fn is_tar_gz(bytes: &[u8]) -> bool {
+    let mut header = [0u8; 512];
+
+    flate2::read::GzDecoder::new(bytes)
+        .read_exact(&mut header)
+        .is_ok_and(|_| &header[257..262] == b"ustar")
+}
+
+/// Читает оглавление архива
+
+pub fn list(kind: Kind, bytes: &[u8]) -> Result<Vec<Entry>, String> {
+    match kind {
+        Kind::Zip => {
+            let mut archive =
+                zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(|e| e.to_string())?;
+
+            (0..archive.len())
+                .map(|index| {
+                    let file = archive.by_index_raw(index).map_err(|e| e.to_string())?;
+
+                    Ok(Entry {
+                        name: file.name().to_string(),
+                        size: file.size(),
+                        modified: file.last_modified().map(|t| {
+                            format!(
+                                "{:04}-{:02}-{:02} {:02}:{:02}",
+                                t.year(),
+                                t.month(),
+                                t.day(),
+                                t.hour(),
+                                t.minute()
+                            )
+                        }),
+                        directory: file.is_dir(),
+                    })
+                })
+                .collect()
+        }
+        Kind::Tar | Kind::TarGz => {
+            let mut entries = Vec::new();
+
+            let mut archive = tar::Archive::new(tar_reader(kind, bytes));
+
+            for entry in archive.entries().map_err(|e| e.to_string())? {
+                let entry = entry.map_err(|e| e.to_string())?;
+
+                let header = entry.header();
+
+                entries.push(Entry {
+                    name: String::from_utf8_lossy(&entry.path_bytes()).into_owned(),
+                    size: header.size().unwrap_or(0),
+                    modified: header.mtime().ok().map(|t| format_unix_time(t as i64)),
+                    directory: header.entry_type().is_dir(),
+                });
+            }
+
+            Ok(entries)
+        }
+        Kind::SevenZip => {
+            let reader = sevenz_rust2::ArchiveReader::new(
+                std::io::Cursor::new(bytes),
+                sevenz_rust2::Password::empty(),
+            )
+            .map_err(|e| e.to_string())?;
+
+            Ok(reader
+                .archive()
+                .files
+                .iter()
+                .map(|file| Entry {
+                    name: file.name.clone(),
+                    size: file.size,
+                    modified: file.has_last_modified_date.then(|| {
+                        let time: std::time::SystemTime = file.last_modified_date.into();
+
+                        let seconds = time
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .map_or(0, |d| d.as_secs() as i64);
+
+                        format_unix_time(seconds)
+                    }),
+                    directory: file.is_directory,
+                })
+                .collect())
+        }
+    }
+}
+
+/// Извлекает содержимое одного файла архива по полному имени
+
+pub fn member(kind: Kind, bytes: &[u8], name: &str) -> Result<Option<Vec<u8>>, String> {
+    let mut data = Vec::new();
+
+    match kind {
+        Kind::Zip => {
+            let mut archive =
+                zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(|e| e.to_string())?;
+
+            let mut file = match archive.by_name(name) {
+                Ok(file) => file,
+                Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+                Err(e) => return Err(e.to_string()),
+            };
+
+            file.read_to_end(&mut data).map_err(|e| e.to_string())?;
+        }
+        Kind::Tar | Kind::TarGz => {
+            let mut archive = tar::Archive::new(tar_reader(kind, bytes));
+
+            let mut found = false;
+
+            for entry in archive.entries().map_err(|e| e.to_string())? {
+                let mut entry = entry.map_err(|e| e.to_string())?;
+
+                if *entry.path_bytes() == *name.as_bytes() {
+                    entry.read_to_end(&mut data).map_err(|e| e.to_string())?;
+
+                    found = true;
+
+                    break;
+                }
+            }
+
+            if !found {
+                return Ok(None);
+            }
+        }
+        Kind::SevenZip => {
+            let mut reader = sevenz_rust2::ArchiveReader::new(
+                std::io::Cursor::new(bytes),
+                sevenz_rust2::Password::empty(),
+            )
+            .map_err(|e| e.to_string())?;
+
+            if !reader.archive().files.iter().any(|f| f.name == name) {
+                return Ok(None);
+            }
+
+            data = reader.read_file(name).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(Some(data))
+}
+
+fn tar_reader(kind: Kind, bytes: &[u8]) -> Box<dyn Read + '_> {
+    match kind {
+        Kind::TarGz => Box::new(flate2::read::GzDecoder::new(bytes)),
+        _ => Box::new(bytes),
+    }
+}
+
+/// Форматирует время UNIX в виде `ГГГГ-ММ-ДД ЧЧ:ММ` (UTC)
+
+fn format_unix_time(seconds: i64) -> String {
+    let days = seconds.div_euclid(86_400);
+
+    let time = seconds.rem_euclid(86_400);
+
+    // Перевод числа дней от 1970-01-01 в дату григорианского календаря
+    let z = days + 719_468;
+
+    let era = z.div_euclid(146_097);
+
+    let day_of_era = z.rem_euclid(146_097);
+
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+
+    let mp = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60
+    )
+}
@@ -0,0 +1,545 @@
+use file_format::FileFormat;
+
+/// Код неизвестного формата
+
+pub const UNKNOWN: u16 = 0;
+
+/// Код уже зашифрованных данных
+
+pub const CRYPTED: u16 = 1;
+
+/// Код текста с разделителями-запятыми (CSV), в `file_format` отдельного формата нет
+
+pub const CSV: u16 = 0xFF00;
+
+/// Код текста с разделителями-табуляциями (TSV)
+
+pub const TSV: u16 = 0xFF01;
+
+/// Коды структурированных текстовых форматов, не различаемых `file_format`
+
+pub const JSON: u16 = 0xFF02;
+
+pub const YAML: u16 = 0xFF03;
+
+pub const TOML: u16 = 0xFF04;
+
+/// Таблица кодов форматов, записываемых в заголовок (2 байта).
+///
+/// Коды стабильны: 0-3 совпадают с однобайтовыми кодами первых версий заголовка,
+/// новые форматы `file_format` добавляются только в конец таблицы.
+
+pub const TABLE: &[(u16, FileFormat)] = &[
+    (2, FileFormat::PlainText),
+    (3, FileFormat::OfficeOpenXmlDocument),
+    (4, FileFormat::Abiword),
+    (5, FileFormat::AbiwordTemplate),
+    (6, FileFormat::Ace),
+    (7, FileFormat::ActionsMediaVideo),
+    (8, FileFormat::Activemime),
+    (9, FileFormat::AdaptableScalableTextureCompression),
+    (10, FileFormat::AdaptiveMultiRate),
+    (11, FileFormat::AdditiveManufacturingFormat),
+    (12, FileFormat::AdobeIllustratorArtwork),
+    (13, FileFormat::AdobeIndesignDocument),
+    (14, FileFormat::AdobeIntegratedRuntime),
+    (15, FileFormat::AdobePhotoshopDocument),
+    (16, FileFormat::AdvancedAudioCoding),
+    (17, FileFormat::AdvancedStreamRedirector),
+    (18, FileFormat::AdvancedSystemsFormat),
+    (19, FileFormat::AgeEncryption),
+    (20, FileFormat::Alz),
+    (21, FileFormat::AmigaDiskFile),
+    (22, FileFormat::AndroidAppBundle),
+    (23, FileFormat::AndroidBinaryXml),
+    (24, FileFormat::AndroidPackage),
+    (25, FileFormat::AndroidResourceStorageContainer),
+    (26, FileFormat::AnimatedPortableNetworkGraphics),
+    (27, FileFormat::ApacheArrowColumnar),
+    (28, FileFormat::ApacheAvro),
+    (29, FileFormat::ApacheParquet),
+    (30, FileFormat::Appimage),
+    (31, FileFormat::AppleDiskImage),
+    (32, FileFormat::AppleIconImage),
+    (33, FileFormat::AppleItunesAudio),
+    (34, FileFormat::AppleItunesAudiobook),
+    (35, FileFormat::AppleItunesProtectedAudio),
+    (36, FileFormat::AppleItunesVideo),
+    (37, FileFormat::AppleQuicktime),
+    (38, FileFormat::ArbitraryBinaryData),
+    (39, FileFormat::ArchivedByRobertJung),
+    (40, FileFormat::Atari7800Rom),
+    (41, FileFormat::Atom),
+    (42, FileFormat::Au),
+    (43, FileFormat::AudioCodec3),
+    (44, FileFormat::AudioInterchangeFileFormat),
+    (45, FileFormat::AudioVideoInterleave),
+    (46, FileFormat::AudioVisualResearch),
+    (47, FileFormat::AutocadDrawing),
+    (48, FileFormat::Autodesk123d),
+    (49, FileFormat::AutodeskAlias),
+    (50, FileFormat::AutodeskAnimator),
+    (51, FileFormat::AutodeskAnimatorPro),
+    (52, FileFormat::AutodeskInventorAssembly),
+    (53, FileFormat::AutodeskInventorDrawing),
+    (54, FileFormat::AutodeskInventorPart),
+    (55, FileFormat::AutodeskInventorPresentation),
+    (56, FileFormat::Av1ImageFileFormat),
+    (57, FileFormat::Av1ImageFileFormatSequence),
+    (58, FileFormat::BdavMpeg2TransportStream),
+    (59, FileFormat::BetterPortableGraphics),
+    (60, FileFormat::Bittorrent),
+    (61, FileFormat::Blender),
+    (62, FileFormat::BmfontAscii),
+    (63, FileFormat::BmfontBinary),
+    (64, FileFormat::BroadBandEbook),
+    (65, FileFormat::Bzip),
+    (66, FileFormat::Bzip2),
+    (67, FileFormat::Bzip3),
+    (68, FileFormat::Cabinet),
+    (69, FileFormat::CanonRaw),
+    (70, FileFormat::CanonRaw2),
+    (71, FileFormat::CanonRaw3),
+    (72, FileFormat::CdAudio),
+    (73, FileFormat::Cinema4d),
+    (74, FileFormat::Cineon),
+    (75, FileFormat::CircuitDiagramDocument),
+    (76, FileFormat::ClojureScript),
+    (77, FileFormat::CollaborativeDesignActivity),
+    (78, FileFormat::Commodore64Cartridge),
+    (79, FileFormat::Commodore64Program),
+    (80, FileFormat::CommonObjectFileFormat),
+    (81, FileFormat::CompoundFileBinary),
+    (82, FileFormat::CorelPresentations),
+    (83, FileFormat::CorelPresentations7),
+    (84, FileFormat::Cpio),
+    (85, FileFormat::CreativeVoice),
+    (86, FileFormat::DalvikExecutable),
+    (87, FileFormat::DebianPackage),
+    (88, FileFormat::DerCertificate),
+    (89, FileFormat::DesignWebFormat),
+    (90, FileFormat::DesignWebFormatXps),
+    (91, FileFormat::DigitalImagingAndCommunicationsInMedicine),
+    (92, FileFormat::DigitalPictureExchange),
+    (93, FileFormat::Djvu),
+    (94, FileFormat::DrawingExchangeFormatAscii),
+    (95, FileFormat::DrawingExchangeFormatBinary),
+    (96, FileFormat::Drawio),
+    (97, FileFormat::DynamicLinkLibrary),
+    (98, FileFormat::EightBitSampledVoice),
+    (99, FileFormat::ElectronicPublication),
+    (100, FileFormat::EmbeddedOpentype),
+    (101, FileFormat::Empty),
+    (102, FileFormat::EncapsulatedPostscript),
+    (103, FileFormat::EnhancedMetafile),
+    (104, FileFormat::EnterpriseApplicationArchive),
+    (105, FileFormat::ExecutableAndLinkableFormat),
+    (106, FileFormat::ExperimentalComputingFacility),
+    (107, FileFormat::Extensible3d),
+    (108, FileFormat::ExtensibleArchive),
+    (109, FileFormat::ExtensibleBinaryMetaLanguage),
+    (110, FileFormat::ExtensibleMarkupLanguage),
+    (111, FileFormat::ExtensibleStylesheetLanguageTransformations),
+    (112, FileFormat::Farbfeld),
+    (113, FileFormat::Fasttracker2ExtendedModule),
+    (114, FileFormat::Fictionbook),
+    (115, FileFormat::FictionbookZip),
+    (116, FileFormat::FigletFont),
+    (117, FileFormat::FigmaDesign),
+    (118, FileFormat::Filmbox),
+    (119, FileFormat::FlashCs5Project),
+    (120, FileFormat::FlashMp4Audio),
+    (121, FileFormat::FlashMp4Audiobook),
+    (122, FileFormat::FlashMp4ProtectedVideo),
+    (123, FileFormat::FlashMp4Video),
+    (124, FileFormat::FlashProject),
+    (125, FileFormat::FlashVideo),
+    (126, FileFormat::FlexibleAndInteroperableDataTransfer),
+    (127, FileFormat::FlexibleImageTransportSystem),
+    (128, FileFormat::FreeLosslessAudioCodec),
+    (129, FileFormat::FreeLosslessImageFormat),
+    (130, FileFormat::FujifilmRaw),
+    (131, FileFormat::Fusion360),
+    (132, FileFormat::GameBoyAdvanceRom),
+    (133, FileFormat::GameBoyColorRom),
+    (134, FileFormat::GameBoyRom),
+    (135, FileFormat::GameGearRom),
+    (136, FileFormat::GeographyMarkupLanguage),
+    (137, FileFormat::GettextMachineObject),
+    (138, FileFormat::GlTransmissionFormatBinary),
+    (139, FileFormat::Glyphs),
+    (140, FileFormat::GoogleChromeExtension),
+    (141, FileFormat::GoogleDraco),
+    (142, FileFormat::GpsExchangeFormat),
+    (143, FileFormat::GraphicsInterchangeFormat),
+    (144, FileFormat::Gzip),
+    (145, FileFormat::HighEfficiencyImageCoding),
+    (146, FileFormat::HighEfficiencyImageCodingSequence),
+    (147, FileFormat::HighEfficiencyImageFileFormat),
+    (148, FileFormat::HighEfficiencyImageFileFormatSequence),
+    (149, FileFormat::HypertextMarkupLanguage),
+    (150, FileFormat::Icalendar),
+    (151, FileFormat::IccProfile),
+    (152, FileFormat::Id3v2),
+    (153, FileFormat::ImpulseTrackerModule),
+    (154, FileFormat::IndesignMarkupLanguage),
+    (155, FileFormat::InitialGraphicsExchangeSpecification),
+    (156, FileFormat::InterQuakeExport),
+    (157, FileFormat::InterQuakeModel),
+    (158, FileFormat::IosAppStorePackage),
+    (159, FileFormat::Iso9660),
+    (160, FileFormat::JavaArchive),
+    (161, FileFormat::JavaClass),
+    (162, FileFormat::JavaKeystore),
+    (163, FileFormat::JointPhotographicExpertsGroup),
+    (164, FileFormat::Jpeg2000Codestream),
+    (165, FileFormat::Jpeg2000Part1),
+    (166, FileFormat::Jpeg2000Part2),
+    (167, FileFormat::Jpeg2000Part3),
+    (168, FileFormat::Jpeg2000Part6),
+    (169, FileFormat::JpegExtendedRange),
+    (170, FileFormat::JpegLs),
+    (171, FileFormat::JpegNetworkGraphics),
+    (172, FileFormat::JpegXl),
+    (173, FileFormat::JsonFeed),
+    (174, FileFormat::KeyholeMarkupLanguage),
+    (175, FileFormat::KeyholeMarkupLanguageZip),
+    (176, FileFormat::KhronosTexture),
+    (177, FileFormat::KhronosTexture2),
+    (178, FileFormat::Larc),
+    (179, FileFormat::Latex),
+    (180, FileFormat::LempelZivFiniteStateEntropy),
+    (181, FileFormat::LempelZivMarkovChainAlgorithm),
+    (182, FileFormat::Lha),
+    (183, FileFormat::LinearExecutable),
+    (184, FileFormat::LlvmBitcode),
+    (185, FileFormat::LongRangeZip),
+    (186, FileFormat::LuaBytecode),
+    (187, FileFormat::LuaScript),
+    (188, FileFormat::Lz4),
+    (189, FileFormat::Lzip),
+    (190, FileFormat::Lzop),
+    (191, FileFormat::MachO),
+    (192, FileFormat::MacosAlias),
+    (193, FileFormat::Magicavoxel),
+    (194, FileFormat::MagickImageFileFormat),
+    (195, FileFormat::MaterialExchangeFormat),
+    (196, FileFormat::MathematicalMarkupLanguage),
+    (197, FileFormat::Matroska3dVideo),
+    (198, FileFormat::MatroskaAudio),
+    (199, FileFormat::MatroskaSubtitles),
+    (200, FileFormat::MatroskaVideo),
+    (201, FileFormat::MayaAscii),
+    (202, FileFormat::MayaBinary),
+    (203, FileFormat::MegaDriveRom),
+    (204, FileFormat::MetaInformationEncapsulation),
+    (205, FileFormat::MicrosoftAccess2007Database),
+    (206, FileFormat::MicrosoftAccessDatabase),
+    (207, FileFormat::MicrosoftCompiledHtmlHelp),
+    (208, FileFormat::MicrosoftDigitalVideoRecording),
+    (209, FileFormat::MicrosoftDirectdrawSurface),
+    (210, FileFormat::MicrosoftExcelSpreadsheet),
+    (211, FileFormat::MicrosoftPowerpointPresentation),
+    (212, FileFormat::MicrosoftProjectPlan),
+    (213, FileFormat::MicrosoftPublisherDocument),
+    (214, FileFormat::MicrosoftReader),
+    (215, FileFormat::MicrosoftSoftwareInstaller),
+    (216, FileFormat::MicrosoftVirtualHardDisk),
+    (217, FileFormat::MicrosoftVirtualHardDisk2),
+    (218, FileFormat::MicrosoftVisioDrawing),
+    (219, FileFormat::MicrosoftVisualStudioExtension),
+    (220, FileFormat::MicrosoftVisualStudioSolution),
+    (221, FileFormat::MicrosoftWordDocument),
+    (222, FileFormat::MicrosoftWorks6Spreadsheet),
+    (223, FileFormat::MicrosoftWorksDatabase),
+    (224, FileFormat::MicrosoftWorksSpreadsheet),
+    (225, FileFormat::MicrosoftWorksWordProcessor),
+    (226, FileFormat::MicrosoftWrite),
+    (227, FileFormat::Mobipocket),
+    (228, FileFormat::Model3dAscii),
+    (229, FileFormat::Model3dBinary),
+    (230, FileFormat::MonkeysAudio),
+    (231, FileFormat::MozillaArchive),
+    (232, FileFormat::Mp3Url),
+    (233, FileFormat::Mpeg12AudioLayer2),
+    (234, FileFormat::Mpeg12AudioLayer3),
+    (235, FileFormat::Mpeg12Video),
+    (236, FileFormat::Mpeg2TransportStream),
+    (237, FileFormat::Mpeg4Part14),
+    (238, FileFormat::Mpeg4Part14Audio),
+    (239, FileFormat::Mpeg4Part14Subtitles),
+    (240, FileFormat::Mpeg4Part14Video),
+    (241, FileFormat::MpegDashMpd),
+    (242, FileFormat::MsDosBatch),
+    (243, FileFormat::MsDosExecutable),
+    (244, FileFormat::Mtv),
+    (245, FileFormat::MultiLayerArchive),
+    (246, FileFormat::MultipleImageNetworkGraphics),
+    (247, FileFormat::Musepack),
+    (248, FileFormat::MusicalInstrumentDigitalInterface),
+    (249, FileFormat::Musicxml),
+    (250, FileFormat::MusicxmlZip),
+    (251, FileFormat::NeoGeoPocketColorRom),
+    (252, FileFormat::NeoGeoPocketRom),
+    (253, FileFormat::NewExecutable),
+    (254, FileFormat::NikonElectronicFile),
+    (255, FileFormat::Nintendo64Rom),
+    (256, FileFormat::NintendoDsRom),
+    (257, FileFormat::NintendoEntertainmentSystemRom),
+    (258, FileFormat::NintendoSwitchExecutable),
+    (259, FileFormat::NintendoSwitchPackage),
+    (260, FileFormat::NintendoSwitchRom),
+    (261, FileFormat::OfficeOpenXmlDrawing),
+    (262, FileFormat::OfficeOpenXmlPresentation),
+    (263, FileFormat::OfficeOpenXmlSpreadsheet),
+    (264, FileFormat::OggFlac),
+    (265, FileFormat::OggMedia),
+    (266, FileFormat::OggMultiplexedMedia),
+    (267, FileFormat::OggOpus),
+    (268, FileFormat::OggSpeex),
+    (269, FileFormat::OggTheora),
+    (270, FileFormat::OggVorbis),
+    (271, FileFormat::OlympusRawFormat),
+    (272, FileFormat::OpendocumentDatabase),
+    (273, FileFormat::OpendocumentFormula),
+    (274, FileFormat::OpendocumentFormulaTemplate),
+    (275, FileFormat::OpendocumentGraphics),
+    (276, FileFormat::OpendocumentGraphicsTemplate),
+    (277, FileFormat::OpendocumentPresentation),
+    (278, FileFormat::OpendocumentPresentationTemplate),
+    (279, FileFormat::OpendocumentSpreadsheet),
+    (280, FileFormat::OpendocumentSpreadsheetTemplate),
+    (281, FileFormat::OpendocumentText),
+    (282, FileFormat::OpendocumentTextMaster),
+    (283, FileFormat::OpendocumentTextMasterTemplate),
+    (284, FileFormat::OpendocumentTextTemplate),
+    (285, FileFormat::Openexr),
+    (286, FileFormat::Opennurbs),
+    (287, FileFormat::Openraster),
+    (288, FileFormat::Opentype),
+    (289, FileFormat::Openxps),
+    (290, FileFormat::OptimizedDalvikExecutable),
+    (291, FileFormat::PanasonicRaw),
+    (292, FileFormat::PcapDump),
+    (293, FileFormat::PcapNextGenerationDump),
+    (294, FileFormat::PemCertificate),
+    (295, FileFormat::PemCertificateSigningRequest),
+    (296, FileFormat::PemPrivateKey),
+    (297, FileFormat::PemPublicKey),
+    (298, FileFormat::PerlScript),
+    (299, FileFormat::PersonalStorageTable),
+    (300, FileFormat::PgpMessage),
+    (301, FileFormat::PgpPrivateKeyBlock),
+    (302, FileFormat::PgpPublicKeyBlock),
+    (303, FileFormat::PgpSignature),
+    (304, FileFormat::PgpSignedMessage),
+    (305, FileFormat::PictureExchange),
+    (306, FileFormat::Pmarc),
+    (307, FileFormat::PolygonAscii),
+    (308, FileFormat::PolygonBinary),
+    (309, FileFormat::PortableArbitraryMap),
+    (310, FileFormat::PortableBitmap),
+    (311, FileFormat::PortableDocumentFormat),
+    (312, FileFormat::PortableExecutable),
+    (313, FileFormat::PortableFloatmap),
+    (314, FileFormat::PortableGraymap),
+    (315, FileFormat::PortableNetworkGraphics),
+    (316, FileFormat::PortablePixmap),
+    (317, FileFormat::Postscript),
+    (318, FileFormat::PythonScript),
+    (319, FileFormat::QemuCopyOnWrite),
+    (320, FileFormat::QualcommPurevoice),
+    (321, FileFormat::QuiteOkAudio),
+    (322, FileFormat::QuiteOkImage),
+    (323, FileFormat::RadianceHdr),
+    (324, FileFormat::Realaudio),
+    (325, FileFormat::ReallySimpleSyndication),
+    (326, FileFormat::Realmedia),
+    (327, FileFormat::Realvideo),
+    (328, FileFormat::RedHatPackageManager),
+    (329, FileFormat::RichTextFormat),
+    (330, FileFormat::RoshalArchive),
+    (331, FileFormat::RubyScript),
+    (332, FileFormat::Rzip),
+    (333, FileFormat::ScalableVectorGraphics),
+    (334, FileFormat::ScreamTracker3Module),
+    (335, FileFormat::SegaMasterSystemRom),
+    (336, FileFormat::Seqbox),
+    (337, FileFormat::SevenZip),
+    (338, FileFormat::Shapefile),
+    (339, FileFormat::ShellScript),
+    (340, FileFormat::ShoutcastPlaylist),
+    (341, FileFormat::SiliconGraphicsImage),
+    (342, FileFormat::SiliconGraphicsMovie),
+    (343, FileFormat::SimpleObjectAccessProtocol),
+    (344, FileFormat::Sketch),
+    (345, FileFormat::Sketch43),
+    (346, FileFormat::Sketchup),
+    (347, FileFormat::SmallWebFormat),
+    (348, FileFormat::Snappy),
+    (349, FileFormat::SolidworksAssembly),
+    (350, FileFormat::SolidworksDrawing),
+    (351, FileFormat::SolidworksPart),
+    (352, FileFormat::SonyDsdStreamFile),
+    (353, FileFormat::SonyMovie),
+    (354, FileFormat::Soundfont2),
+    (355, FileFormat::SpaceclaimDocument),
+    (356, FileFormat::Sqlite3),
+    (357, FileFormat::Squashfs),
+    (358, FileFormat::StandardForTheExchangeOfProductModelData),
+    (359, FileFormat::Starcalc),
+    (360, FileFormat::Starchart),
+    (361, FileFormat::Stardraw),
+    (362, FileFormat::Starimpress),
+    (363, FileFormat::Starmath),
+    (364, FileFormat::Starwriter),
+    (365, FileFormat::StereolithographyAscii),
+    (366, FileFormat::Stuffit),
+    (367, FileFormat::StuffitX),
+    (368, FileFormat::SubripText),
+    (369, FileFormat::SunXmlCalc),
+    (370, FileFormat::SunXmlCalcTemplate),
+    (371, FileFormat::SunXmlDraw),
+    (372, FileFormat::SunXmlDrawTemplate),
+    (373, FileFormat::SunXmlImpress),
+    (374, FileFormat::SunXmlImpressTemplate),
+    (375, FileFormat::SunXmlMath),
+    (376, FileFormat::SunXmlWriter),
+    (377, FileFormat::SunXmlWriterGlobal),
+    (378, FileFormat::SunXmlWriterTemplate),
+    (379, FileFormat::TagImageFileFormat),
+    (380, FileFormat::TapeArchive),
+    (381, FileFormat::Tasty),
+    (382, FileFormat::ThirdGenerationPartnershipProject),
+    (383, FileFormat::ThirdGenerationPartnershipProject2),
+    (384, FileFormat::ThreeDimensionalManufacturingFormat),
+    (385, FileFormat::ThreeDimensionalStudio),
+    (386, FileFormat::ThreeDimensionalStudioMax),
+    (387, FileFormat::TiledMapXml),
+    (388, FileFormat::TiledTilesetXml),
+    (389, FileFormat::TimedTextMarkupLanguage),
+    (390, FileFormat::ToolCommandLanguageScript),
+    (391, FileFormat::TrainingCenterXml),
+    (392, FileFormat::Truetype),
+    (393, FileFormat::TruetypeCollection),
+    (394, FileFormat::UltimateSoundtrackerModule),
+    (395, FileFormat::UniformOfficeFormatPresentation),
+    (396, FileFormat::UniformOfficeFormatSpreadsheet),
+    (397, FileFormat::UniformOfficeFormatText),
+    (398, FileFormat::Universal3d),
+    (399, FileFormat::UniversalSceneDescriptionAscii),
+    (400, FileFormat::UniversalSceneDescriptionBinary),
+    (401, FileFormat::UniversalSceneDescriptionZip),
+    (402, FileFormat::UniversalSubtitleFormat),
+    (403, FileFormat::UnixArchiver),
+    (404, FileFormat::UnixCompress),
+    (405, FileFormat::Vcalendar),
+    (406, FileFormat::Vcard),
+    (407, FileFormat::VirtualMachineDisk),
+    (408, FileFormat::VirtualRealityModelingLanguage),
+    (409, FileFormat::VirtualboxVirtualDiskImage),
+    (410, FileFormat::WaveformAudio),
+    (411, FileFormat::Wavpack),
+    (412, FileFormat::WebApplicationArchive),
+    (413, FileFormat::WebOpenFontFormat),
+    (414, FileFormat::WebOpenFontFormat2),
+    (415, FileFormat::WebVideoTextTracks),
+    (416, FileFormat::WebassemblyBinary),
+    (417, FileFormat::WebassemblyText),
+    (418, FileFormat::Webm),
+    (419, FileFormat::Webp),
+    (420, FileFormat::WindowsAnimatedCursor),
+    (421, FileFormat::WindowsAppBundle),
+    (422, FileFormat::WindowsAppPackage),
+    (423, FileFormat::WindowsBitmap),
+    (424, FileFormat::WindowsCursor),
+    (425, FileFormat::WindowsIcon),
+    (426, FileFormat::WindowsImagingFormat),
+    (427, FileFormat::WindowsMediaAudio),
+    (428, FileFormat::WindowsMediaPlaylist),
+    (429, FileFormat::WindowsMediaVideo),
+    (430, FileFormat::WindowsMetafile),
+    (431, FileFormat::WindowsRecordedTvShow),
+    (432, FileFormat::WindowsShortcut),
+    (433, FileFormat::WordperfectDocument),
+    (434, FileFormat::WordperfectGraphics),
+    (435, FileFormat::WordperfectMacro),
+    (436, FileFormat::WordperfectPresentations),
+    (437, FileFormat::XPixmap),
+    (438, FileFormat::Xap),
+    (439, FileFormat::Xbox360Executable),
+    (440, FileFormat::XboxExecutable),
+    (441, FileFormat::XmlLocalizationInterchangeFileFormat),
+    (442, FileFormat::XmlShareablePlaylistFormat),
+    (443, FileFormat::Xpinstall),
+    (444, FileFormat::Xz),
+    (445, FileFormat::Zip),
+    (446, FileFormat::Zoo),
+    (447, FileFormat::Zpaq),
+    (448, FileFormat::Zstandard),
+];
+
+pub fn to_code(format: FileFormat) -> Option<u16> {
+    TABLE
+        .iter()
+        .find(|(_, f)| *f == format)
+        .map(|(code, _)| *code)
+}
+
+/// Поиск формата по короткому имени, полному имени или расширению без учета регистра
+
+pub fn find(name: &str) -> Option<FileFormat> {
+    let formats = || TABLE.iter().map(|(_, format)| *format);
+
+    formats()
+        .find(|f| f.short_name().is_some_and(|n| n.eq_ignore_ascii_case(name)))
+        .or_else(|| formats().find(|f| f.name().eq_ignore_ascii_case(name)))
+        .or_else(|| formats().find(|f| f.extension().eq_ignore_ascii_case(name)))
+}
+
+pub fn from_code(code: u16) -> Option<FileFormat> {
+    TABLE
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, format)| *format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique() {
+        let mut codes: Vec<u16> = TABLE.iter().map(|(code, _)| *code).collect();
+
+        codes.extend_from_slice(&[UNKNOWN, CRYPTED, CSV, TSV, JSON, YAML, TOML]);
+
+        let total = codes.len();
+
+        codes.sort_unstable();
+
+        codes.dedup();
+
+        assert_eq!(codes.len(), total);
+    }
+
+    #[test]
+    fn formats_are_unique() {
+        for (index, (_, format)) in TABLE.iter().enumerate() {
+            assert!(
+                TABLE[..index].iter().all(|(_, other)| other != format),
+                "{:?} встречается в таблице дважды",
+                format
+            );
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for (code, format) in TABLE {
+            assert_eq!(to_code(*format), Some(*code));
+
+            assert_eq!(from_code(to_code(*format).unwrap()), Some(*format));
+        }
+    }
+}
@@ -1,59 +1,518 @@
 use docx_rs::{DocumentChild, ParagraphChild, RunChild};
 
-/// Рекурсивно извлекает текст из документа
+/// Извлекает текст документа вместе с колонтитулами, нумерацией списков и сносками
+
+pub fn extract_text_from_docx(bytes: &[u8]) -> Result<String, String> {
+    let docx = docx_rs::read_docx(bytes).map_err(|e| e.to_string())?;
+
+    let mut numbering = ListNumbering::read(bytes)?;
+
+    let section = &docx.document.section_property;
 
-pub fn extract_text_from_docx(docx: &docx_rs::Docx) -> String {
     let mut result = String::new();
 
+    let headers: Vec<&docx_rs::Header> =
+        [&section.first_header, &section.header, &section.even_header]
+            .into_iter()
+            .flatten()
+            .collect();
+
+    for header in headers {
+        let text = extract_text_from_header(&header.children, &mut numbering);
+
+        push_section(&mut result, "Верхний колонтитул", &text);
+    }
+
     for element in &docx.document.children {
         match element {
             DocumentChild::Paragraph(p) => {
-                result.push_str(&extract_text_from_paragraph(p));
+                result.push_str(&extract_numbered_paragraph(p, &mut numbering));
 
                 result.push('\n');
             }
-            DocumentChild::Table(t) => {
-                for row in &t.rows {
-                    if let docx_rs::TableChild::TableRow(row) = row {
-                        // Теперь 'row' - это TableRow, и ячейки лежат в row.cells
-                        for cell in &row.cells {
-                            if let docx_rs::TableRowChild::TableCell(cell) = cell {
-                                for content in &cell.children {
-                                    // Внутри ячейки таблицы
-                                    if let docx_rs::TableCellContent::Paragraph(p) = content {
-                                        result.push_str(&extract_text_from_paragraph(p));
-
-                                        result.push(' ');
-                                    }
-                                }
-                            }
-                        }
+            DocumentChild::Table(t) => result.push_str(&extract_text_from_table(t)),
+            _ => {}
+        }
+    }
+
+    let footers: Vec<&docx_rs::Footer> =
+        [&section.first_footer, &section.footer, &section.even_footer]
+            .into_iter()
+            .flatten()
+            .collect();
+
+    for footer in footers {
+        let text = extract_text_from_footer(&footer.children, &mut numbering);
+
+        push_section(&mut result, "Нижний колонтитул", &text);
+    }
+
+    for (part, title) in [
+        ("word/footnotes.xml", "Сноски"),
+        ("word/endnotes.xml", "Концевые сноски"),
+    ] {
+        if let Some(xml) = super::xml::zip_entry(bytes, part)? {
+            push_section(&mut result, title, &extract_notes_from_docx(&xml)?);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Добавляет к тексту именованный раздел, пустые разделы пропускаются
+
+fn push_section(result: &mut String, title: &str, text: &str) {
+    if text.trim().is_empty() {
+        return;
+    }
+
+    result.push_str(&format!("[{}]\n", title));
+
+    result.push_str(text.trim_end());
+
+    result.push('\n');
+}
+
+fn extract_text_from_header(
+    children: &[docx_rs::HeaderChild],
+    numbering: &mut ListNumbering,
+) -> String {
+    let mut text = String::new();
+
+    for child in children {
+        match child {
+            docx_rs::HeaderChild::Paragraph(p) => {
+                text.push_str(&extract_numbered_paragraph(p, numbering));
+
+                text.push('\n');
+            }
+            docx_rs::HeaderChild::Table(t) => text.push_str(&extract_text_from_table(t)),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn extract_text_from_footer(
+    children: &[docx_rs::FooterChild],
+    numbering: &mut ListNumbering,
+) -> String {
+    let mut text = String::new();
+
+    for child in children {
+        match child {
+            docx_rs::FooterChild::Paragraph(p) => {
+                text.push_str(&extract_numbered_paragraph(p, numbering));
+
+                text.push('\n');
+            }
+            docx_rs::FooterChild::Table(t) => text.push_str(&extract_text_from_table(t)),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Извлекает текст таблицы, ячейки разделяются пробелом, строки - переводом строки
+
+fn extract_text_from_table(t: &docx_rs::Table) -> String {
+    let mut result = String::new();
+
+    for row in &t.rows {
+        let docx_rs::TableChild::TableRow(row) = row;
+
+        for cell in &row.cells {
+            let docx_rs::TableRowChild::TableCell(cell) = cell;
+
+            for content in &cell.children {
+                // Внутри ячейки таблицы
+                if let docx_rs::TableCellContent::Paragraph(p) = content {
+                    result.push_str(&extract_text_from_paragraph(p));
+
+                    result.push(' ');
+                }
+            }
+        }
+
+        result.push('\n'); // Новая строка после каждой строки таблицы
+    }
+
+    result
+}
+
+/// Извлекает текст параграфа с отступом и меткой элемента списка
+
+fn extract_numbered_paragraph(p: &docx_rs::Paragraph, numbering: &mut ListNumbering) -> String {
+    let text = extract_text_from_paragraph(p);
+
+    let Some(docx_rs::NumberingProperty {
+        id: Some(id),
+        level,
+    }) = &p.property.numbering_property
+    else {
+        return text;
+    };
+
+    let level = level.as_ref().map_or(0, |l| l.val);
+
+    if level > MAX_LIST_LEVEL {
+        return text;
+    }
+
+    match numbering.label(id.id, level) {
+        Some(label) => format!("{}{} {}", "  ".repeat(level), label, text),
+        None => text,
+    }
+}
+
+/// Извлекает текст сносок из `footnotes.xml` или `endnotes.xml`.
+///
+/// Служебные разделители сносок пропускаются.
+
+fn extract_notes_from_docx(xml: &[u8]) -> Result<String, String> {
+    let mut result = String::new();
+
+    let mut note: Option<String> = None;
+
+    let mut number = 0;
+
+    super::xml::walk(xml, |node| match node {
+        super::xml::Node::Start("footnote" | "endnote", attrs) => {
+            let service = attrs
+                .get("type")
+                .is_some_and(|t| t != "normal" && !t.is_empty());
+
+            note = (!service).then(String::new);
+        }
+        super::xml::Node::End("footnote" | "endnote") => {
+            if let Some(text) = note.take() {
+                number += 1;
+
+                result.push_str(&format!("[{}] {}\n", number, text.trim()));
+            }
+        }
+        super::xml::Node::Start("tab", _) => {
+            if let Some(text) = &mut note {
+                text.push('\t');
+            }
+        }
+        super::xml::Node::End("p") => {
+            if let Some(text) = &mut note {
+                text.push(' ');
+            }
+        }
+        super::xml::Node::Text(t) => {
+            if let Some(text) = &mut note {
+                text.push_str(t);
+            }
+        }
+        _ => {}
+    })?;
+
+    Ok(result)
+}
+
+/// Последний уровень списка: Word нумерует уровни с 0 по 8, большие номера
+/// встречаются лишь в поврежденных документах и не должны раздувать счетчики
+
+const MAX_LIST_LEVEL: usize = 8;
+
+/// Уровень списка: начальное значение, формат номера и шаблон метки вида `%1.%2.`
+
+#[derive(Default, Clone)]
+
+struct ListLevel {
+    start: usize,
+    format: String,
+    text: String,
+}
+
+/// Нумерация списков документа из `numbering.xml` со счетчиками по каждому списку
+
+#[derive(Default)]
+
+struct ListNumbering {
+    abstracts: std::collections::HashMap<usize, Vec<ListLevel>>,
+    lists: std::collections::HashMap<usize, (usize, std::collections::HashMap<usize, usize>)>,
+    counters: std::collections::HashMap<usize, Vec<Option<usize>>>,
+}
+
+impl ListNumbering {
+    fn read(bytes: &[u8]) -> Result<Self, String> {
+        let mut numbering = Self::default();
+
+        let Some(xml) = super::xml::zip_entry(bytes, "word/numbering.xml")? else {
+            return Ok(numbering);
+        };
 
-                        result.push('\n'); // Новая строка после каждой строки таблицы
+        let mut abstract_id: Option<usize> = None;
+
+        let mut list_id: Option<usize> = None;
+
+        // `None` - уровень с недопустимым номером, его записи пропускаются
+        let mut level: Option<usize> = Some(0);
+
+        let number = |attrs: &std::collections::HashMap<String, String>, key: &str| {
+            attrs.get(key).and_then(|v| v.parse::<usize>().ok())
+        };
+
+        super::xml::walk(&xml, |node| match node {
+            super::xml::Node::Start("abstractNum", attrs) => {
+                abstract_id = number(attrs, "abstractNumId");
+            }
+            super::xml::Node::End("abstractNum") => abstract_id = None,
+            super::xml::Node::Start("num", attrs) => list_id = number(attrs, "numId"),
+            super::xml::Node::End("num") => list_id = None,
+            super::xml::Node::Start("lvl" | "lvlOverride", attrs) => {
+                level = number(attrs, "ilvl")
+                    .or(Some(0))
+                    .filter(|level| *level <= MAX_LIST_LEVEL);
+            }
+            super::xml::Node::End("lvl" | "lvlOverride") => level = Some(0),
+            super::xml::Node::Start(name, attrs) => {
+                let Some(level) = level else {
+                    return;
+                };
+
+                let value = attrs.get("val").cloned().unwrap_or_default();
+
+                if let Some(id) = abstract_id {
+                    let levels = numbering.abstracts.entry(id).or_default();
+
+                    if levels.len() <= level {
+                        levels.resize(level + 1, ListLevel::default());
+                    }
+
+                    match name {
+                        "start" => levels[level].start = value.parse().unwrap_or(0),
+                        "numFmt" => levels[level].format = value,
+                        "lvlText" => levels[level].text = value,
+                        _ => {}
+                    }
+                } else if let Some(id) = list_id {
+                    let list = numbering.lists.entry(id).or_default();
+
+                    match name {
+                        "abstractNumId" => list.0 = value.parse().unwrap_or(0),
+                        "startOverride" => {
+                            list.1.insert(level, value.parse().unwrap_or(0));
+                        }
+                        _ => {}
                     }
                 }
             }
             _ => {}
+        })?;
+
+        Ok(numbering)
+    }
+
+    /// Следующая метка элемента списка, более глубокие уровни начинаются заново
+
+    fn label(&mut self, id: usize, level: usize) -> Option<String> {
+        let (abstract_id, overrides) = self.lists.get(&id)?;
+
+        let levels = self.abstracts.get(abstract_id)?;
+
+        let start = |l: usize| {
+            overrides
+                .get(&l)
+                .copied()
+                .or_else(|| levels.get(l).map(|l| l.start))
+                .unwrap_or(1)
+        };
+
+        let counters = self.counters.entry(id).or_default();
+
+        if counters.len() <= level {
+            counters.resize(level + 1, None);
+        }
+
+        counters[level] = Some(counters[level].map_or(start(level), |c| c + 1));
+
+        counters.truncate(level + 1);
+
+        let current = levels.get(level)?;
+
+        if current.format == "bullet" {
+            return Some(String::from("•"));
+        }
+
+        if current.format == "none" {
+            return Some(String::new());
+        }
+
+        let mut label = current.text.clone();
+
+        for (l, counter) in counters.iter().enumerate() {
+            let value = counter.unwrap_or(start(l));
+
+            let format = levels.get(l).map_or("decimal", |l| l.format.as_str());
+
+            label = label.replace(&format!("%{}", l + 1), &format_number(value, format));
         }
+
+        Some(label)
     }
+}
+
+/// Номер элемента списка в заданном формате Word
+
+fn format_number(value: usize, format: &str) -> String {
+    match format {
+        "lowerLetter" | "upperLetter" => {
+            let mut n = value.max(1);
+
+            let mut letters = Vec::new();
+
+            while n > 0 {
+                n -= 1;
+
+                letters.push((b'a' + (n % 26) as u8) as char);
+
+                n /= 26;
+            }
+
+            let letters: String = letters.into_iter().rev().collect();
+
+            if format == "upperLetter" {
+                letters.to_uppercase()
+            } else {
+                letters
+            }
+        }
+        "lowerRoman" => roman(value).to_lowercase(),
+        "upperRoman" => roman(value),
+        _ => value.to_string(),
+    }
+}
+
+fn roman(mut value: usize) -> String {
+    const DIGITS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut result = String::new();
+
+    for (weight, digit) in DIGITS {
+        while value >= weight {
+            result.push_str(digit);
+
+            value -= weight;
+        }
+    }
+
+    result
+}
+
+thread_local! {
+    /// Паника в этом потоке перехватывается `catch_silently` и не печатается
+    static SILENT_PANIC: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Перехватывает панику без вывода сообщения в stderr.
+///
+/// Обработчик паники общий для процесса, а документы разбираются в нескольких
+/// потоках, поэтому обработчик подменяется один раз и молчит только в потоке,
+/// который сейчас внутри `catch_silently`. Паники остальных потоков печатаются как обычно.
+
+fn catch_silently<T>(f: impl FnOnce() -> T + std::panic::UnwindSafe) -> std::thread::Result<T> {
+    static HOOK: std::sync::Once = std::sync::Once::new();
+
+    HOOK.call_once(|| {
+        let default = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            if !SILENT_PANIC.get() {
+                default(info);
+            }
+        }));
+    });
+
+    let silent = SILENT_PANIC.replace(true);
+
+    let result = std::panic::catch_unwind(f);
+
+    SILENT_PANIC.set(silent);
 
     result
 }
 
+/// Извлекает текст PDF документа постранично, не записывая его на диск
+
+pub fn extract_pages_from_pdf(bytes: &[u8]) -> Result<Vec<String>, String> {
+    // Разбор поврежденных PDF может завершиться паникой внутри библиотеки
+    match catch_silently(|| pdf_extract::extract_text_from_mem_by_pages(bytes)) {
+        Ok(Ok(pages)) => Ok(pages),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(String::from("структура документа повреждена")),
+    }
+}
+
 /// Извлекает текст из конкретного параграфа
 
 pub fn extract_text_from_paragraph(p: &docx_rs::Paragraph) -> String {
     let mut text = String::new();
 
     for child in &p.children {
-        if let ParagraphChild::Run(run) = child {
-            for run_child in &run.children {
-                if let RunChild::Text(t) = run_child {
-                    text.push_str(&t.text);
+        extract_text_from_paragraph_child(child, &mut text);
+    }
+
+    text
+}
+
+fn extract_text_from_paragraph_child(child: &ParagraphChild, text: &mut String) {
+    match child {
+        ParagraphChild::Run(run) => extract_text_from_run(run, text),
+        ParagraphChild::Hyperlink(link) => {
+            for child in &link.children {
+                extract_text_from_paragraph_child(child, text);
+            }
+        }
+        ParagraphChild::Insert(insert) => {
+            for child in &insert.children {
+                if let docx_rs::InsertChild::Run(run) = child {
+                    extract_text_from_run(run, text);
                 }
             }
         }
+        _ => {}
     }
+}
 
-    text
+fn extract_text_from_run(run: &docx_rs::Run, text: &mut String) {
+    for run_child in &run.children {
+        match run_child {
+            RunChild::Text(t) => text.push_str(&t.text),
+            RunChild::Tab(_) | RunChild::PTab(_) => text.push('\t'),
+            RunChild::Break(_) => text.push('\n'),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catch_silently_returns_panic_as_error() {
+        assert!(catch_silently(|| panic!("поврежденный документ")).is_err());
+
+        assert_eq!(catch_silently(|| 7).ok(), Some(7));
+    }
 }
@@ -0,0 +1,243 @@
+/// Подпись блока FIB документа Word 97-2003
+
+const WORD_IDENT: u16 = 0xA5EC;
+
+/// Извлекает текст документа Word 97-2003 (DOC) по таблице фрагментов.
+///
+/// Форматирование не разбирается: выводится основной текст, затем сноски и колонтитулы.
+/// Документы Word 6/95 и зашифрованные документы не поддерживаются.
+
+pub fn extract_text(bytes: &[u8]) -> Result<String, String> {
+    let mut file =
+        cfb::CompoundFile::open(std::io::Cursor::new(bytes)).map_err(|e| e.to_string())?;
+
+    let word = read_stream(&mut file, "/WordDocument")?;
+
+    if u16_at(&word, 0) != Some(WORD_IDENT) {
+        return Err(String::from("нет подписи документа Word"));
+    }
+
+    let flags = u16_at(&word, 0x0A).unwrap_or(0);
+
+    if flags & 0x0100 != 0 {
+        return Err(String::from("документ защищен паролем"));
+    }
+
+    let table = read_stream(
+        &mut file,
+        if flags & 0x0200 != 0 {
+            "/1Table"
+        } else {
+            "/0Table"
+        },
+    )?;
+
+    // Блок FIB: FibBase (32 байта), затем массивы переменной длины с счетчиками перед ними
+    let csw = u16_at(&word, 32).ok_or_else(broken)? as usize;
+
+    let lw = 32 + 2 + csw * 2;
+
+    let cslw = u16_at(&word, lw).ok_or_else(broken)? as usize;
+
+    let fc_lcb = lw + 2 + cslw * 4 + 2;
+
+    let counter = |index: usize| u32_at(&word, lw + 2 + index * 4).unwrap_or(0) as usize;
+
+    // Длины частей текста по порядку: основной текст, сноски, колонтитулы
+    let parts = [
+        (counter(3), None),
+        (counter(4), Some("Сноски")),
+        (counter(5), Some("Колонтитулы")),
+    ];
+
+    let fc_clx = u32_at(&word, fc_lcb + 33 * 8).ok_or_else(broken)? as usize;
+
+    let lcb_clx = u32_at(&word, fc_lcb + 33 * 8 + 4).ok_or_else(broken)? as usize;
+
+    let clx = table.get(fc_clx..fc_clx + lcb_clx).ok_or_else(broken)?;
+
+    let pieces = pieces(clx)?;
+
+    let mut result = String::new();
+
+    let mut cp = 0;
+
+    for (len, title) in parts {
+        let text = clean(&text_range(&word, &pieces, cp, cp + len));
+
+        cp += len;
+
+        match title {
+            None => result.push_str(&text),
+            Some(title) if !text.trim().is_empty() => {
+                result.push_str(&format!("[{}]\n{}\n", title, text.trim()))
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(result)
+}
+
+/// Фрагмент текста: диапазон позиций символов, смещение в потоке и признак 8-битной кодировки
+
+struct Piece {
+    start: usize,
+    end: usize,
+    offset: usize,
+    compressed: bool,
+}
+
+/// Разбирает таблицу фрагментов (Clx): пропускает блоки Prc и читает Pcdt
+
+fn pieces(clx: &[u8]) -> Result<Vec<Piece>, String> {
+    let mut i = 0;
+
+    while clx.get(i) == Some(&0x01) {
+        let len = u16_at(clx, i + 1).ok_or_else(broken)? as usize;
+
+        i += 3 + len;
+    }
+
+    if clx.get(i) != Some(&0x02) {
+        return Err(broken());
+    }
+
+    let lcb = u32_at(clx, i + 1).ok_or_else(broken)? as usize;
+
+    let plc = clx.get(i + 5..i + 5 + lcb).ok_or_else(broken)?;
+
+    // PlcPcd: n + 1 позиций символов по 4 байта и n описателей по 8 байт
+    let count = lcb.saturating_sub(4) / 12;
+
+    (0..count)
+        .map(|n| {
+            let start = u32_at(plc, n * 4).ok_or_else(broken)? as usize;
+
+            let end = u32_at(plc, (n + 1) * 4).ok_or_else(broken)? as usize;
+
+            let fc = u32_at(plc, (count + 1) * 4 + n * 8 + 2).ok_or_else(broken)?;
+
+            let compressed = fc & 0x4000_0000 != 0;
+
+            let fc = (fc & 0x3FFF_FFFF) as usize;
+
+            Ok(Piece {
+                start,
+                end,
+                offset: if compressed { fc / 2 } else { fc },
+                compressed,
+            })
+        })
+        .collect()
+}
+
+/// Собирает текст для диапазона позиций символов `[from, to)`
+
+fn text_range(word: &[u8], pieces: &[Piece], from: usize, to: usize) -> String {
+    let mut result = String::new();
+
+    for piece in pieces {
+        let start = piece.start.max(from);
+
+        let end = piece.end.min(to);
+
+        if start >= end {
+            continue;
+        }
+
+        let skip = start - piece.start;
+
+        let count = end - start;
+
+        if piece.compressed {
+            let bytes = word
+                .get(piece.offset + skip..piece.offset + skip + count)
+                .unwrap_or_default();
+
+            result.push_str(
+                &encoding_rs::WINDOWS_1252
+                    .decode_without_bom_handling(bytes)
+                    .0,
+            );
+        } else {
+            let bytes = word
+                .get(piece.offset + skip * 2..piece.offset + (skip + count) * 2)
+                .unwrap_or_default();
+
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+
+            result.push_str(&String::from_utf16_lossy(&units));
+        }
+    }
+
+    result
+}
+
+/// Заменяет служебные символы Word: абзацы, ячейки таблиц и поля.
+///
+/// Из полей остается только вычисленный результат, код поля отбрасывается.
+
+fn clean(text: &str) -> String {
+    let mut result = String::new();
+
+    // Для каждого открытого поля: выводится ли сейчас его результат
+    let mut fields: Vec<bool> = Vec::new();
+
+    for c in text.chars() {
+        match c {
+            '\u{13}' => fields.push(false),
+            '\u{14}' => {
+                if let Some(field) = fields.last_mut() {
+                    *field = true;
+                }
+            }
+            '\u{15}' => {
+                fields.pop();
+            }
+            _ if fields.iter().any(|shown| !shown) => {}
+            '\r' | '\u{0B}' | '\u{0C}' => result.push('\n'),
+            '\u{07}' => result.push('\t'),
+            '\u{1E}' => result.push('-'),
+            '\t' | '\n' => result.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+fn read_stream<F>(file: &mut cfb::CompoundFile<F>, name: &str) -> Result<Vec<u8>, String>
+where
+    F: std::io::Read + std::io::Seek,
+{
+    let mut stream = file
+        .open_stream(name)
+        .map_err(|e| format!("{}: {}", name, e))?;
+
+    let mut data = Vec::new();
+
+    std::io::Read::read_to_end(&mut stream, &mut data).map_err(|e| e.to_string())?;
+
+    Ok(data)
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn broken() -> String {
+    String::from("структура документа повреждена")
+}
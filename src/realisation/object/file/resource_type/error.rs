@@ -3,7 +3,6 @@
 pub enum Error {
     UnknowFormat,
     BrokenByteFormat,
-    ReadError,
 }
 
 impl core::fmt::Display for Error {
@@ -13,7 +12,6 @@ impl core::fmt::Display for Error {
             Self::BrokenByteFormat => {
                 write!(f, "Ошибка подписи байта формата файла магического числа")
             }
-            Self::ReadError => write!(f, "Ошибка чтения файла"),
         }
     }
 }
@@ -23,7 +21,6 @@ impl core::error::Error for Error {
         match self {
             Self::UnknowFormat => None,
             Self::BrokenByteFormat => None,
-            Self::ReadError => None,
         }
     }
 }
@@ -0,0 +1,44 @@
+/// Число байт в строке дампа
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Сколько байт показывать в дампе по умолчанию для неизвестных форматов
+
+pub const PREVIEW_LENGTH: usize = 256;
+
+/// Строки дампа в стиле `xxd`: адрес, байты группами по два и ASCII.
+///
+/// `base` - смещение первого байта `bytes` в исходных данных, используется для адресов.
+
+pub fn lines(bytes: &[u8], base: usize) -> impl Iterator<Item = String> + '_ {
+    bytes
+        .chunks(BYTES_PER_LINE)
+        .enumerate()
+        .map(move |(index, chunk)| {
+            let mut hex = String::with_capacity(BYTES_PER_LINE * 3);
+
+            for i in 0..BYTES_PER_LINE {
+                match chunk.get(i) {
+                    Some(byte) => hex.push_str(&format!("{:02x}", byte)),
+                    None => hex.push_str("  "),
+                }
+
+                if i % 2 == 1 {
+                    hex.push(' ');
+                }
+            }
+
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+
+            format!("{:08x}: {} {}", base + index * BYTES_PER_LINE, hex, ascii)
+        })
+}
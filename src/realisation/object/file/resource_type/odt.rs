@@ -0,0 +1,138 @@
+use super::xml::Node;
+
+/// Извлекает текст документа OpenDocument (ODT) с колонтитулами и сносками
+
+pub fn extract_text(bytes: &[u8]) -> Result<String, String> {
+    let content = super::xml::zip_entry(bytes, "content.xml")?
+        .ok_or_else(|| String::from("в документе нет content.xml"))?;
+
+    let mut text = Text::default();
+
+    super::xml::walk(&content, |node| text.push(node))?;
+
+    let mut result = text.body;
+
+    if let Some(styles) = super::xml::zip_entry(bytes, "styles.xml")? {
+        let mut headers = Text::default();
+
+        let mut footers = Text::default();
+
+        let mut target: Option<bool> = None;
+
+        super::xml::walk(&styles, |node| {
+            match node {
+                Node::Start("header" | "header-left" | "header-first", _) => target = Some(true),
+                Node::Start("footer" | "footer-left" | "footer-first", _) => target = Some(false),
+                Node::End(
+                    "header" | "header-left" | "header-first" | "footer" | "footer-left"
+                    | "footer-first",
+                ) => target = None,
+                _ => {}
+            }
+
+            match target {
+                Some(true) => headers.push(node),
+                Some(false) => footers.push(node),
+                None => {}
+            }
+        })?;
+
+        result = format!(
+            "{}{}{}",
+            section("Верхний колонтитул", &headers.body),
+            result,
+            section("Нижний колонтитул", &footers.body)
+        );
+    }
+
+    result.push_str(&section("Сноски", &text.notes));
+
+    Ok(result)
+}
+
+fn section(title: &str, text: &str) -> String {
+    if text.trim().is_empty() {
+        return String::new();
+    }
+
+    format!("[{}]\n{}\n", title, text.trim_end())
+}
+
+/// Накопитель текста: основной поток и сноски, вынесенные из него
+
+#[derive(Default)]
+
+struct Text {
+    body: String,
+    notes: String,
+    lists: usize,
+    note: Option<String>,
+    citation: Option<String>,
+    skip: usize,
+}
+
+impl Text {
+    fn push(&mut self, node: Node) {
+        match node {
+            // Примечания рецензентов и служебные последовательности не относятся к тексту
+            Node::Start("annotation" | "tracked-changes" | "sequence-decls", _) => self.skip += 1,
+            Node::End("annotation" | "tracked-changes" | "sequence-decls") => {
+                self.skip = self.skip.saturating_sub(1)
+            }
+            _ if self.skip > 0 => {}
+            Node::Start("note", _) => self.note = Some(String::new()),
+            Node::End("note") => {
+                if let Some(note) = self.note.take() {
+                    self.notes.push_str(note.trim());
+
+                    self.notes.push('\n');
+                }
+            }
+            Node::Start("note-citation", _) => self.citation = Some(String::new()),
+            Node::End("note-citation") => {
+                // Номер сноски ставится и в тексте, и перед самой сноской
+                let number = self.citation.take().unwrap_or_default();
+
+                self.body.push_str(&format!("[{}]", number));
+
+                self.note = Some(format!("[{}] ", number));
+            }
+            Node::Start("list", _) => self.lists += 1,
+            Node::End("list") => self.lists = self.lists.saturating_sub(1),
+            Node::Start("list-item", _) => {
+                let indent = "  ".repeat(self.lists.saturating_sub(1));
+
+                self.out().push_str(&format!("{}• ", indent));
+            }
+            Node::Start("s", attrs) => {
+                let count = attrs.get("c").and_then(|c| c.parse().ok()).unwrap_or(1);
+
+                self.out().push_str(&" ".repeat(count));
+            }
+            Node::Start("tab", _) => self.out().push('\t'),
+            Node::Start("line-break", _) => self.out().push('\n'),
+            Node::End("p" | "h") => self.out().push('\n'),
+            Node::End("table-cell") => {
+                let out = self.out();
+
+                if out.ends_with('\n') {
+                    out.pop();
+                }
+
+                out.push(' ');
+            }
+            Node::End("table-row") => self.out().push('\n'),
+            Node::Text(t) => self.out().push_str(t),
+            _ => {}
+        }
+    }
+
+    /// Текущий приемник текста: сноска или основной поток
+    fn out(&mut self) -> &mut String {
+        match (&mut self.citation, &mut self.note) {
+            (Some(citation), _) => citation,
+            (None, Some(note)) => note,
+            (None, None) => &mut self.body,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use image::ImageDecoder;
+
+/// Поля EXIF, выводимые в сводке, с подписями
+
+const EXIF_FIELDS: &[(exif::Tag, &str)] = &[
+    (exif::Tag::Make, "Производитель"),
+    (exif::Tag::Model, "Модель"),
+    (exif::Tag::DateTimeOriginal, "Дата съемки"),
+    (exif::Tag::Orientation, "Ориентация"),
+    (exif::Tag::ExposureTime, "Выдержка"),
+    (exif::Tag::FNumber, "Диафрагма"),
+    (exif::Tag::PhotographicSensitivity, "ISO"),
+    (exif::Tag::FocalLength, "Фокусное расстояние"),
+    (exif::Tag::Software, "Программа"),
+    (exif::Tag::GPSLatitude, "Широта"),
+    (exif::Tag::GPSLongitude, "Долгота"),
+];
+
+/// Наибольшая высота предпросмотра в строках терминала
+
+const MAX_PREVIEW_LINES: usize = 60;
+
+/// Сводка об изображении, полученная без полного декодирования пикселей
+
+pub struct Summary {
+    pub width: u32,
+    pub height: u32,
+    pub color: String,
+    pub exif: Vec<(&'static str, String)>,
+}
+
+/// Читает размеры, тип цвета и основные поля EXIF изображения
+
+pub fn summary(bytes: &[u8]) -> Result<Summary, String> {
+    let decoder = image::ImageReader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?
+        .into_decoder()
+        .map_err(|e| e.to_string())?;
+
+    let (width, height) = decoder.dimensions();
+
+    let color = format!("{:?}", decoder.color_type());
+
+    // EXIF необязателен: его отсутствие или повреждение не мешает показу изображения
+    let exif = exif::Reader::new()
+        .read_from_container(&mut std::io::Cursor::new(bytes))
+        .map(|exif| {
+            EXIF_FIELDS
+                .iter()
+                .filter_map(|(tag, label)| {
+                    let field = exif.get_field(*tag, exif::In::PRIMARY)?;
+
+                    Some((*label, field.display_value().with_unit(&exif).to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(Summary {
+        width,
+        height,
+        color,
+        exif,
+    })
+}
+
+/// Строит предпросмотр изображения полублоками `▀` с 24-битным цветом ANSI.
+///
+/// Каждый символ передает два пикселя по вертикали, ширина задается в символах.
+
+pub fn preview(bytes: &[u8], width: usize) -> Result<Vec<String>, String> {
+    let picture = image::load_from_memory(bytes).map_err(|e| e.to_string())?;
+
+    let (source_width, source_height) = (picture.width().max(1), picture.height().max(1));
+
+    let columns = width.clamp(1, source_width as usize);
+
+    let rows = ((columns as u64 * source_height as u64 / source_width as u64) as usize)
+        .clamp(2, MAX_PREVIEW_LINES * 2);
+
+    let columns =
+        columns.min((rows as u64 * source_width as u64 / source_height as u64).max(1) as usize);
+
+    let small = image::imageops::thumbnail(&picture.to_rgba8(), columns as u32, rows as u32);
+
+    // Прозрачные пиксели смешиваются с черным фоном
+    let pixel = |x: u32, y: u32| -> [u8; 3] {
+        if y >= small.height() {
+            return [0, 0, 0];
+        }
+
+        let [r, g, b, a] = small.get_pixel(x, y).0;
+
+        let blend = |c: u8| (c as u16 * a as u16 / 255) as u8;
+
+        [blend(r), blend(g), blend(b)]
+    };
+
+    Ok((0..small.height())
+        .step_by(2)
+        .map(|y| {
+            let mut line = String::new();
+
+            for x in 0..small.width() {
+                let [tr, tg, tb] = pixel(x, y);
+
+                let [br, bg, bb] = pixel(x, y + 1);
+
+                line.push_str(&format!(
+                    "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀",
+                    tr, tg, tb, br, bg, bb
+                ));
+            }
+
+            line.push_str("\x1b[0m");
+
+            line
+        })
+        .collect())
+}
@@ -0,0 +1,504 @@
+use super::{ResourceType, archive, common, doc, hex, odt, picture, rtf, structured, table};
+use crate::abstraction::{Block, Renderer, Renderers, ViewOptions};
+use file_format::FileFormat;
+
+/// Обработчики всех форматов, которые умеет показывать `read`
+
+pub fn renderers() -> Renderers<ResourceType> {
+    let mut renderers = Renderers::new(Binary);
+
+    renderers
+        .register(
+            &[ResourceType::FileFormat(FileFormat::PlainText)],
+            PlainText,
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::OfficeOpenXmlDocument)],
+            Document("DOCX", common::extract_text_from_docx),
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::OpendocumentText)],
+            Document("ODT", odt::extract_text),
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::RichTextFormat)],
+            Document("RTF", rtf::extract_text),
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::MicrosoftWordDocument)],
+            Document("DOC", doc::extract_text),
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::PortableDocumentFormat)],
+            Pdf,
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::Zip)],
+            Archive(archive::Kind::Zip),
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::TapeArchive)],
+            Archive(archive::Kind::Tar),
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::Gzip)],
+            Archive(archive::Kind::TarGz),
+        )
+        .register(
+            &[ResourceType::FileFormat(FileFormat::SevenZip)],
+            Archive(archive::Kind::SevenZip),
+        )
+        .register(
+            &[
+                ResourceType::FileFormat(FileFormat::PortableNetworkGraphics),
+                ResourceType::FileFormat(FileFormat::AnimatedPortableNetworkGraphics),
+                ResourceType::FileFormat(FileFormat::JointPhotographicExpertsGroup),
+                ResourceType::FileFormat(FileFormat::GraphicsInterchangeFormat),
+                ResourceType::FileFormat(FileFormat::Webp),
+            ],
+            Picture,
+        )
+        .register(
+            &[
+                ResourceType::FileFormat(FileFormat::OfficeOpenXmlSpreadsheet),
+                ResourceType::FileFormat(FileFormat::OpendocumentSpreadsheet),
+                ResourceType::FileFormat(FileFormat::MicrosoftExcelSpreadsheet),
+            ],
+            Spreadsheet,
+        )
+        .register(&[ResourceType::Csv, ResourceType::Tsv], Delimited)
+        .register(
+            &[ResourceType::FileFormat(
+                FileFormat::ExtensibleMarkupLanguage,
+            )],
+            Xml,
+        )
+        .register(&[ResourceType::Json], Structured(structured::Syntax::Json))
+        .register(&[ResourceType::Yaml], Structured(structured::Syntax::Yaml))
+        .register(&[ResourceType::Toml], Structured(structured::Syntax::Toml));
+
+    renderers
+}
+
+/// Шестнадцатеричный дамп диапазона `--offset`/`--length`.
+///
+/// Без `--hex` и `--length` показываются первые `hex::PREVIEW_LENGTH` байт.
+
+pub struct Binary;
+
+impl Renderer<ResourceType> for Binary {
+    fn render(
+        &self,
+        format: &ResourceType,
+        bytes: &[u8],
+        options: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        let heading = match format {
+            _ if options.hex => format!("Дешифрованные данные в HEX (Формат: {:?})", format),
+            ResourceType::FileFormat(f) => {
+                format!("Дешифрованные БИНАРНЫЕ ДАННЫЕ (Формат: {:?})", f)
+            }
+            _ => String::from("Дешифрованные БИНАРНЫЕ ДАННЫЕ (Неизвестный тип)"),
+        };
+
+        let mut blocks = vec![Block::Heading(heading)];
+
+        if options.offset > bytes.len() {
+            blocks.push(Block::Error(format!(
+                "Смещение {:#x} за пределами данных ({} байт)",
+                options.offset,
+                bytes.len()
+            )));
+
+            return blocks;
+        }
+
+        let rest = &bytes[options.offset..];
+
+        let default_length = (!options.hex).then_some(hex::PREVIEW_LENGTH);
+
+        let length = match options.length.or(default_length) {
+            Some(0) | None => rest.len(),
+            Some(length) => length.min(rest.len()),
+        };
+
+        blocks.push(Block::Text(
+            hex::lines(&rest[..length], options.offset)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ));
+
+        if length < bytes.len() {
+            blocks.push(Block::Note(format!(
+                "показано байт {:#x}..{:#x} из {}, диапазон задается --offset и --length",
+                options.offset,
+                options.offset + length,
+                bytes.len()
+            )));
+        }
+
+        blocks
+    }
+}
+
+pub struct PlainText;
+
+impl Renderer<ResourceType> for PlainText {
+    fn render(
+        &self,
+        _: &ResourceType,
+        bytes: &[u8],
+        _: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        vec![
+            Block::Heading(String::from("Дешифрованный ТЕКСТ (TXT)")),
+            Block::Text(String::from_utf8_lossy(bytes).into_owned()),
+        ]
+    }
+}
+
+/// Текстовый документ: название формата и функция извлечения текста
+
+pub struct Document(pub &'static str, pub fn(&[u8]) -> Result<String, String>);
+
+impl Renderer<ResourceType> for Document {
+    fn render(
+        &self,
+        _: &ResourceType,
+        bytes: &[u8],
+        _: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        let Self(title, extract) = self;
+
+        vec![
+            Block::Heading(format!("Дешифрованный {}", title)),
+            match extract(bytes) {
+                Ok(text) => Block::Text(text),
+                Err(e) => Block::Error(format!("Ошибка парсинга {} файла: {}", title, e)),
+            },
+        ]
+    }
+}
+
+/// PDF постранично
+
+pub struct Pdf;
+
+impl Renderer<ResourceType> for Pdf {
+    fn render(
+        &self,
+        _: &ResourceType,
+        bytes: &[u8],
+        _: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        let mut blocks = vec![Block::Heading(String::from("Дешифрованный PDF"))];
+
+        match common::extract_pages_from_pdf(bytes) {
+            Ok(pages) => {
+                let total = pages.len();
+
+                for (number, page) in pages.iter().enumerate() {
+                    blocks.push(Block::Heading(format!(
+                        "Страница {} из {}",
+                        number + 1,
+                        total
+                    )));
+
+                    blocks.push(Block::Text(page.trim().to_string()));
+                }
+            }
+            Err(e) => blocks.push(Block::Error(format!("Ошибка парсинга PDF файла: {}", e))),
+        }
+
+        blocks
+    }
+}
+
+/// Оглавление архива или, если задан `--member`, один его файл.
+///
+/// Файл архива показывается тем же набором обработчиков, что и расшифрованные данные.
+
+pub struct Archive(pub archive::Kind);
+
+impl Renderer<ResourceType> for Archive {
+    fn render(
+        &self,
+        format: &ResourceType,
+        bytes: &[u8],
+        options: &ViewOptions,
+        renderers: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        let Self(kind) = *self;
+
+        // Gzip показывается как архив, только если внутри TAR
+        if matches!(kind, archive::Kind::TarGz) && !archive::is_tar_gz(bytes) {
+            return renderers.render_fallback(format, bytes, options);
+        }
+
+        if let Some(name) = &options.member {
+            return match archive::member(kind, bytes, name) {
+                Ok(Some(data)) => {
+                    let options = ViewOptions {
+                        member: None,
+                        ..options.clone()
+                    };
+
+                    let format: ResourceType =
+                        crate::abstraction::ResourceTypeList::detect(&data, name);
+
+                    let mut blocks = vec![Block::Heading(format!("Файл архива: {}", name))];
+
+                    blocks.extend(renderers.render(&format, &data, &options));
+
+                    blocks
+                }
+                Ok(None) => vec![Block::Error(format!("Файл {} не найден в архиве", name))],
+                Err(e) => vec![Block::Error(format!("Ошибка чтения архива: {}", e))],
+            };
+        }
+
+        let mut blocks = vec![Block::Heading(format!("Дешифрованный АРХИВ ({:?})", kind))];
+
+        match archive::list(kind, bytes) {
+            Ok(entries) => {
+                let mut rows = vec![vec![
+                    String::from("Размер"),
+                    String::from("Изменен"),
+                    String::from("Имя"),
+                ]];
+
+                rows.extend(entries.iter().map(|entry| {
+                    vec![
+                        if entry.directory {
+                            String::from("<каталог>")
+                        } else {
+                            entry.size.to_string()
+                        },
+                        entry.modified.clone().unwrap_or_else(|| String::from("-")),
+                        entry.name.clone(),
+                    ]
+                }));
+
+                blocks.push(Block::Table(rows));
+
+                let files = entries.iter().filter(|e| !e.directory);
+
+                blocks.push(Block::Note(format!(
+                    "всего файлов: {}, {} байт",
+                    files.clone().count(),
+                    files.map(|e| e.size).sum::<u64>()
+                )));
+            }
+            Err(e) => blocks.push(Block::Error(format!("Ошибка чтения архива: {}", e))),
+        }
+
+        blocks
+    }
+}
+
+/// Сводка об изображении и, по запросу, его предпросмотр
+
+pub struct Picture;
+
+impl Renderer<ResourceType> for Picture {
+    fn render(
+        &self,
+        format: &ResourceType,
+        bytes: &[u8],
+        options: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        let name = match format {
+            ResourceType::FileFormat(f) => f.short_name().unwrap_or(f.name()),
+            _ => "?",
+        };
+
+        let mut blocks = vec![Block::Heading(format!(
+            "Дешифрованное ИЗОБРАЖЕНИЕ ({})",
+            name
+        ))];
+
+        match picture::summary(bytes) {
+            Ok(summary) => {
+                let mut fields = vec![
+                    (
+                        String::from("Размер"),
+                        format!("{}x{}", summary.width, summary.height),
+                    ),
+                    (String::from("Цвет"), summary.color),
+                ];
+
+                fields.extend(
+                    summary
+                        .exif
+                        .into_iter()
+                        .map(|(label, value)| (label.to_string(), value)),
+                );
+
+                blocks.push(Block::Fields(fields));
+            }
+            Err(e) => {
+                blocks.push(Block::Error(format!("Ошибка разбора изображения: {}", e)));
+
+                return blocks;
+            }
+        }
+
+        if options.preview {
+            blocks.push(match picture::preview(bytes, options.preview_width) {
+                Ok(lines) => Block::Text(lines.join("\n")),
+                Err(e) => Block::Error(format!("Ошибка построения предпросмотра: {}", e)),
+            });
+        }
+
+        blocks
+    }
+}
+
+/// Лист электронной таблицы (XLSX, ODS, XLS)
+
+pub struct Spreadsheet;
+
+impl Renderer<ResourceType> for Spreadsheet {
+    fn render(
+        &self,
+        _: &ResourceType,
+        bytes: &[u8],
+        options: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        match table::read_spreadsheet(bytes, options.sheet.as_deref()) {
+            Ok(sheet) => {
+                let heading = format!(
+                    "Дешифрованная ТАБЛИЦА, лист \"{}\" (листы: {})",
+                    sheet.name,
+                    sheet.names.join(", ")
+                );
+
+                rows(heading, sheet.rows, options)
+            }
+            Err(e) => vec![Block::Error(format!("Ошибка парсинга таблицы: {}", e))],
+        }
+    }
+}
+
+/// Текст с разделителями (CSV, TSV)
+
+pub struct Delimited;
+
+impl Renderer<ResourceType> for Delimited {
+    fn render(
+        &self,
+        format: &ResourceType,
+        bytes: &[u8],
+        options: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        let delimiter = if matches!(format, ResourceType::Tsv) {
+            b'\t'
+        } else {
+            b','
+        };
+
+        match table::read_delimited(bytes, delimiter) {
+            Ok(data) => rows(
+                format!("Дешифрованная ТАБЛИЦА ({:?})", format),
+                data,
+                options,
+            ),
+            Err(e) => vec![Block::Error(format!("Ошибка парсинга таблицы: {}", e))],
+        }
+    }
+}
+
+/// XML с отступами или, если задан `--query`, одно значение
+
+pub struct Xml;
+
+impl Renderer<ResourceType> for Xml {
+    fn render(
+        &self,
+        _: &ResourceType,
+        bytes: &[u8],
+        options: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        if let Some(path) = &options.query {
+            return vec![match structured::query_xml(bytes, path) {
+                Ok(Some(value)) => Block::Text(value),
+                Ok(None) => Block::Error(format!("Значение {} не найдено", path)),
+                Err(e) => Block::Error(format!("Ошибка парсинга XML: {}", e)),
+            }];
+        }
+
+        vec![
+            Block::Heading(String::from("Дешифрованный XML")),
+            match structured::render_xml(bytes, options.color) {
+                Ok(text) => Block::Text(text),
+                Err(e) => Block::Error(format!("Ошибка парсинга XML: {}", e)),
+            },
+        ]
+    }
+}
+
+/// Структурированные данные с отступами или, если задан `--query`, одно значение.
+///
+/// Значение запроса выводится без заголовка, чтобы его можно было передать другой программе.
+
+pub struct Structured(pub structured::Syntax);
+
+impl Renderer<ResourceType> for Structured {
+    fn render(
+        &self,
+        _: &ResourceType,
+        bytes: &[u8],
+        options: &ViewOptions,
+        _: &Renderers<ResourceType>,
+    ) -> Vec<Block> {
+        let Self(syntax) = *self;
+
+        let value = match structured::parse(syntax, bytes) {
+            Ok(value) => value,
+            Err(e) => {
+                return vec![Block::Error(format!(
+                    "Ошибка парсинга {}: {}",
+                    syntax.name(),
+                    e
+                ))];
+            }
+        };
+
+        if let Some(path) = &options.query {
+            return vec![match structured::query(&value, path) {
+                Some(found) => Block::Text(structured::render_value(found, syntax, options.color)),
+                None => Block::Error(format!("Значение {} не найдено", path)),
+            }];
+        }
+
+        vec![
+            Block::Heading(format!("Дешифрованный {}", syntax.name())),
+            Block::Text(structured::render(&value, syntax, options.color)),
+        ]
+    }
+}
+
+/// Строки таблицы в CSV без заголовка или таблицей с ограничением строк и столбцов
+
+fn rows(heading: String, rows: Vec<Vec<String>>, options: &ViewOptions) -> Vec<Block> {
+    if options.csv {
+        return vec![match table::to_csv(&rows) {
+            Ok(text) => Block::Text(text),
+            Err(e) => Block::Error(format!("Ошибка вывода CSV: {}", e)),
+        }];
+    }
+
+    let mut blocks = vec![Block::Heading(heading)];
+
+    blocks.extend(table::truncate(rows, options.max_rows, options.max_cols));
+
+    blocks
+}
@@ -0,0 +1,255 @@
+/// Группы RTF, не содержащие текста документа
+
+const SKIPPED_DESTINATIONS: &[&str] = &[
+    "fonttbl",
+    "colortbl",
+    "stylesheet",
+    "listtable",
+    "listoverridetable",
+    "revtbl",
+    "rsidtbl",
+    "info",
+    "pict",
+    "object",
+    "fldinst",
+    "themedata",
+    "colorschememapping",
+    "datastore",
+    "latentstyles",
+    "generator",
+    "xmlnstbl",
+    "filetbl",
+];
+
+/// Состояние группы `{...}`: пропуск содержимого и число символов замены после `\u`
+
+#[derive(Clone, Copy)]
+
+struct Group {
+    skip: bool,
+    unicode_skip: usize,
+}
+
+/// Извлекает текст документа RTF.
+///
+/// Однобайтовый текст декодируется по кодовой странице `\ansicpg`, по умолчанию windows-1252.
+
+pub fn extract_text(bytes: &[u8]) -> Result<String, String> {
+    if !bytes.starts_with(b"{\\rtf") {
+        return Err(String::from("нет подписи {\\rtf"));
+    }
+
+    let mut result = String::new();
+
+    let mut pending: Vec<u8> = Vec::new();
+
+    let mut encoding = encoding_rs::WINDOWS_1252;
+
+    let mut group = Group {
+        skip: false,
+        unicode_skip: 1,
+    };
+
+    let mut stack: Vec<Group> = Vec::new();
+
+    // Символы замены, которые еще нужно пропустить после `\u`
+    let mut to_skip: usize = 0;
+
+    // Предыдущий токен - `{`, следующий управляющий символ может быть назначением группы
+    let mut group_start = false;
+
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+
+        let at_group_start = std::mem::take(&mut group_start);
+
+        match byte {
+            b'{' => {
+                stack.push(group);
+
+                group_start = true;
+
+                i += 1;
+            }
+            b'}' => {
+                group = stack.pop().unwrap_or(group);
+
+                to_skip = 0;
+
+                i += 1;
+            }
+            b'\\' => {
+                let (word, param, next) = control(bytes, i + 1);
+
+                i = next;
+
+                if word == "'" {
+                    if to_skip > 0 {
+                        to_skip -= 1;
+                    } else if !group.skip {
+                        pending.extend(param.map(|p| p as u8));
+                    }
+
+                    continue;
+                }
+
+                if to_skip > 0 && word.len() == 1 && !word.chars().all(char::is_alphabetic) {
+                    to_skip -= 1;
+
+                    continue;
+                }
+
+                if word == "*" || (at_group_start && SKIPPED_DESTINATIONS.contains(&word.as_str()))
+                {
+                    group.skip = true;
+
+                    continue;
+                }
+
+                if group.skip {
+                    continue;
+                }
+
+                let text = match word.as_str() {
+                    "ansicpg" => {
+                        if let Some(page) = param.and_then(codepage) {
+                            encoding = page;
+                        }
+
+                        None
+                    }
+                    "uc" => {
+                        group.unicode_skip = param.unwrap_or(1).max(0) as usize;
+
+                        None
+                    }
+                    "u" => {
+                        let code = param.unwrap_or(0);
+
+                        let code = if code < 0 { code + 65536 } else { code } as u32;
+
+                        to_skip = group.unicode_skip;
+
+                        Some(char::from_u32(code).unwrap_or('\u{fffd}').to_string())
+                    }
+                    "par" | "line" | "row" | "sect" | "page" => Some(String::from("\n")),
+                    "tab" | "cell" => Some(String::from("\t")),
+                    "emdash" => Some(String::from("—")),
+                    "endash" => Some(String::from("–")),
+                    "bullet" => Some(String::from("•")),
+                    "lquote" => Some(String::from("‘")),
+                    "rquote" => Some(String::from("’")),
+                    "ldblquote" => Some(String::from("“")),
+                    "rdblquote" => Some(String::from("”")),
+                    "~" => Some(String::from("\u{a0}")),
+                    "_" => Some(String::from("-")),
+                    "\\" | "{" | "}" => Some(word.clone()),
+                    _ => None,
+                };
+
+                if let Some(text) = text {
+                    flush(&mut pending, encoding, &mut result);
+
+                    result.push_str(&text);
+                }
+            }
+            b'\r' | b'\n' => i += 1,
+            _ => {
+                if to_skip > 0 {
+                    to_skip -= 1;
+                } else if !group.skip {
+                    pending.push(byte);
+                }
+
+                i += 1;
+            }
+        }
+    }
+
+    flush(&mut pending, encoding, &mut result);
+
+    Ok(result)
+}
+
+/// Разбирает управляющее слово или символ, начиная с байта после `\`.
+///
+/// Возвращает слово, числовой параметр и позицию следующего токена.
+
+fn control(bytes: &[u8], mut i: usize) -> (String, Option<i32>, usize) {
+    let Some(&first) = bytes.get(i) else {
+        return (String::new(), None, i);
+    };
+
+    if !first.is_ascii_alphabetic() {
+        if first == b'\'' {
+            let hex = bytes.get(i + 1..i + 3).unwrap_or_default();
+
+            let value = std::str::from_utf8(hex)
+                .ok()
+                .and_then(|h| i32::from_str_radix(h, 16).ok());
+
+            return (String::from("'"), value, i + 1 + hex.len());
+        }
+
+        return ((first as char).to_string(), None, i + 1);
+    }
+
+    let start = i;
+
+    while bytes.get(i).is_some_and(u8::is_ascii_alphabetic) {
+        i += 1;
+    }
+
+    let word = String::from_utf8_lossy(&bytes[start..i]).into_owned();
+
+    let number_start = i;
+
+    if bytes.get(i) == Some(&b'-') {
+        i += 1;
+    }
+
+    while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+        i += 1;
+    }
+
+    let param = std::str::from_utf8(&bytes[number_start..i])
+        .ok()
+        .and_then(|n| n.parse().ok());
+
+    // Пробел после управляющего слова - разделитель, а не текст
+    if bytes.get(i) == Some(&b' ') {
+        i += 1;
+    }
+
+    (word, param, i)
+}
+
+/// Декодирует накопленные однобайтовые символы в результат
+
+fn flush(pending: &mut Vec<u8>, encoding: &'static encoding_rs::Encoding, result: &mut String) {
+    if pending.is_empty() {
+        return;
+    }
+
+    result.push_str(&encoding.decode_without_bom_handling(pending).0);
+
+    pending.clear();
+}
+
+/// Кодировка по номеру кодовой страницы Windows
+
+fn codepage(page: i32) -> Option<&'static encoding_rs::Encoding> {
+    let label = match page {
+        866 => String::from("ibm866"),
+        932 => String::from("shift_jis"),
+        936 => String::from("gbk"),
+        949 => String::from("euc-kr"),
+        950 => String::from("big5"),
+        10000 => String::from("macintosh"),
+        page => format!("windows-{}", page),
+    };
+
+    encoding_rs::Encoding::for_label(label.as_bytes())
+}
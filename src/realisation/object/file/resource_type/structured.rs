@@ -0,0 +1,531 @@
+use quick_xml::events::Event;
+use serde_json::Value;
+
+/// Синтаксис структурированных данных
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+
+pub enum Syntax {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl Syntax {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+        }
+    }
+}
+
+/// Цвета ANSI для подсветки: ключи и теги, строки, числа, логические значения и null
+
+const KEY: &str = "\x1b[34m";
+const STRING: &str = "\x1b[32m";
+const NUMBER: &str = "\x1b[36m";
+const KEYWORD: &str = "\x1b[35m";
+const RESET: &str = "\x1b[0m";
+
+/// Разбирает данные в общее дерево значений
+
+pub fn parse(syntax: Syntax, bytes: &[u8]) -> Result<Value, String> {
+    let text = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+
+    match syntax {
+        Syntax::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+        Syntax::Yaml => serde_yaml_ng::from_str::<serde_yaml_ng::Value>(text)
+            .map(from_yaml)
+            .map_err(|e| e.to_string()),
+        Syntax::Toml => text
+            .parse::<toml::Table>()
+            .map(|table| from_toml(toml::Value::Table(table)))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Проверяет, что текст - документ JSON (объект или массив)
+
+pub fn is_json(bytes: &[u8]) -> bool {
+    let start = bytes.iter().find(|b| !b.is_ascii_whitespace());
+
+    matches!(start, Some(b'{' | b'[')) && serde_json::from_slice::<Value>(bytes).is_ok()
+}
+
+fn from_yaml(value: serde_yaml_ng::Value) -> Value {
+    match value {
+        serde_yaml_ng::Value::Null => Value::Null,
+        serde_yaml_ng::Value::Bool(b) => Value::Bool(b),
+        serde_yaml_ng::Value::Number(n) => {
+            serde_json::from_str(&n.to_string()).unwrap_or_else(|_| Value::String(n.to_string()))
+        }
+        serde_yaml_ng::Value::String(s) => Value::String(s),
+        serde_yaml_ng::Value::Sequence(items) => {
+            Value::Array(items.into_iter().map(from_yaml).collect())
+        }
+        serde_yaml_ng::Value::Mapping(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let key = match from_yaml(key) {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+
+                    (key, from_yaml(value))
+                })
+                .collect(),
+        ),
+        serde_yaml_ng::Value::Tagged(tagged) => from_yaml(tagged.value),
+    }
+}
+
+fn from_toml(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(from_toml).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, from_toml(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Шаг пути запроса: имя ключа и необязательный индекс `[n]`
+
+struct Segment {
+    key: String,
+    index: Option<usize>,
+}
+
+/// Разбирает путь вида `database.password` или `servers[0].host`
+
+fn segments(path: &str) -> Vec<Segment> {
+    path.split('.')
+        .filter(|part| !part.is_empty())
+        .map(
+            |part| match part.strip_suffix(']').and_then(|p| p.split_once('[')) {
+                Some((key, index)) if index.parse::<usize>().is_ok() => Segment {
+                    key: key.to_string(),
+                    index: index.parse().ok(),
+                },
+                _ => Segment {
+                    key: part.to_string(),
+                    index: None,
+                },
+            },
+        )
+        .collect()
+}
+
+/// Находит значение по пути, числовой ключ у массива - индекс элемента
+
+pub fn query<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    segments(path).iter().try_fold(value, |value, segment| {
+        let value = if segment.key.is_empty() {
+            value
+        } else {
+            match value {
+                Value::Object(map) => map.get(&segment.key)?,
+                Value::Array(items) => items.get(segment.key.parse::<usize>().ok()?)?,
+                _ => return None,
+            }
+        };
+
+        match segment.index {
+            Some(index) => value.as_array()?.get(index),
+            None => Some(value),
+        }
+    })
+}
+
+/// Печатает дерево в синтаксисе исходного формата: JSON, YAML или TOML
+
+pub fn render(value: &Value, syntax: Syntax, color: bool) -> String {
+    let mut out = String::new();
+
+    match syntax {
+        Syntax::Json => render_json(&mut out, value, 0, color),
+        Syntax::Toml if !is_scalar(value) => render_toml(&mut out, value, color),
+        Syntax::Yaml | Syntax::Toml => match value {
+            Value::Object(map) if !map.is_empty() => render_tree(&mut out, value, 0, color),
+            Value::Array(items) if !items.is_empty() => render_tree(&mut out, value, 0, color),
+            _ => out.push_str(&scalar(value, color)),
+        },
+    }
+
+    out.trim_end().to_string()
+}
+
+/// Значение результата запроса: строки без кавычек, составные значения - деревом
+
+pub fn render_value(value: &Value, syntax: Syntax, color: bool) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        _ => render(value, syntax, color),
+    }
+}
+
+fn paint(text: &str, code: &str, color: bool) -> String {
+    if color {
+        format!("{}{}{}", code, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+fn scalar(value: &Value, color: bool) -> String {
+    match value {
+        Value::String(_) => paint(&value.to_string(), STRING, color),
+        Value::Number(n) => paint(&n.to_string(), NUMBER, color),
+        Value::Bool(_) | Value::Null => paint(&value.to_string(), KEYWORD, color),
+        Value::Array(items) if items.is_empty() => String::from("[]"),
+        Value::Object(map) if map.is_empty() => String::from("{}"),
+        _ => String::new(),
+    }
+}
+
+fn is_scalar(value: &Value) -> bool {
+    match value {
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => true,
+    }
+}
+
+fn render_json(out: &mut String, value: &Value, indent: usize, color: bool) {
+    let pad = "  ".repeat(indent + 1);
+
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            out.push_str("{\n");
+
+            for (n, (key, value)) in map.iter().enumerate() {
+                let key = Value::String(key.clone()).to_string();
+
+                out.push_str(&format!("{}{}: ", pad, paint(&key, KEY, color)));
+
+                render_json(out, value, indent + 1, color);
+
+                out.push_str(if n + 1 < map.len() { ",\n" } else { "\n" });
+            }
+
+            out.push_str(&format!("{}}}", "  ".repeat(indent)));
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push_str("[\n");
+
+            for (n, value) in items.iter().enumerate() {
+                out.push_str(&pad);
+
+                render_json(out, value, indent + 1, color);
+
+                out.push_str(if n + 1 < items.len() { ",\n" } else { "\n" });
+            }
+
+            out.push_str(&format!("{}]", "  ".repeat(indent)));
+        }
+        _ => out.push_str(&scalar(value, color)),
+    }
+}
+
+fn render_tree(out: &mut String, value: &Value, indent: usize, color: bool) {
+    let pad = " ".repeat(indent);
+
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                out.push_str(&format!("{}{}:", pad, paint(key, KEY, color)));
+
+                if is_scalar(value) {
+                    out.push_str(&format!(" {}\n", scalar(value, color)));
+                } else {
+                    out.push('\n');
+
+                    render_tree(out, value, indent + 2, color);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for value in items {
+                if is_scalar(value) {
+                    out.push_str(&format!("{}- {}\n", pad, scalar(value, color)));
+
+                    continue;
+                }
+
+                // Первая строка вложенного значения начинается сразу после маркера `- `
+                let mut nested = String::new();
+
+                render_tree(&mut nested, value, indent + 2, color);
+
+                out.push_str(&format!("{}- {}", pad, &nested[indent + 2..]));
+            }
+        }
+        _ => out.push_str(&format!("{}{}\n", pad, scalar(value, color))),
+    }
+}
+
+/// Печатает таблицу документом TOML, а массив из результата запроса - значением TOML.
+///
+/// Даты TOML хранятся в дереве строками и печатаются в кавычках.
+
+fn render_toml(out: &mut String, value: &Value, color: bool) {
+    let text = match value {
+        Value::Object(_) => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+        _ => toml::Value::try_from(value)
+            .map(|value| value.to_string())
+            .map_err(|e| e.to_string()),
+    };
+
+    // Дерево из TOML всегда сериализуется обратно, дерево YAML - запасной вариант
+    let Ok(text) = text else {
+        return render_tree(out, value, 0, color);
+    };
+
+    for line in text.lines() {
+        if line.starts_with('[') {
+            out.push_str(&paint(line, KEY, color));
+        } else if let Some((key, value)) = line.split_once(" = ") {
+            out.push_str(&format!("{} = {}", paint(key, KEY, color), value));
+        } else {
+            out.push_str(line);
+        }
+
+        out.push('\n');
+    }
+}
+
+/// Переформатирует XML с отступами, сохраняя экранирование исходного текста
+
+pub fn render_xml(bytes: &[u8], color: bool) -> Result<String, String> {
+    let mut reader = quick_xml::Reader::from_reader(bytes);
+
+    let mut events: Vec<Event> = Vec::new();
+
+    // Текст со ссылками на сущности приходит частями, части склеиваются в один узел
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event().map_err(|e| e.to_string())?;
+
+        match &event {
+            Event::Text(e) => {
+                text.push_str(&String::from_utf8_lossy(e));
+
+                continue;
+            }
+            Event::GeneralRef(e) => {
+                text.push_str(&format!("&{};", String::from_utf8_lossy(e)));
+
+                continue;
+            }
+            _ => {}
+        }
+
+        if !text.trim().is_empty() {
+            events.push(Event::Text(quick_xml::events::BytesText::from_escaped(
+                text.trim().to_string(),
+            )));
+        }
+
+        text.clear();
+
+        match event {
+            Event::Eof => break,
+            event => events.push(event),
+        }
+    }
+
+    let mut out = String::new();
+
+    let mut depth = 0;
+
+    let mut i = 0;
+
+    while i < events.len() {
+        let pad = "  ".repeat(depth);
+
+        match &events[i] {
+            Event::Start(e) => {
+                let tag = open_tag(e, false, color);
+
+                // Элемент с единственным текстовым узлом печатается в одну строку
+                if let (Some(Event::Text(text)), Some(Event::End(end))) =
+                    (events.get(i + 1), events.get(i + 2))
+                {
+                    out.push_str(&format!(
+                        "{}{}{}{}\n",
+                        pad,
+                        tag,
+                        String::from_utf8_lossy(text),
+                        close_tag(end.name().as_ref(), color)
+                    ));
+
+                    i += 3;
+
+                    continue;
+                }
+
+                out.push_str(&format!("{}{}\n", pad, tag));
+
+                depth += 1;
+            }
+            Event::Empty(e) => out.push_str(&format!("{}{}\n", pad, open_tag(e, true, color))),
+            Event::End(e) => {
+                depth = depth.saturating_sub(1);
+
+                out.push_str(&format!(
+                    "{}{}\n",
+                    "  ".repeat(depth),
+                    close_tag(e.name().as_ref(), color)
+                ));
+            }
+            Event::Text(text) => {
+                out.push_str(&format!("{}{}\n", pad, String::from_utf8_lossy(text)))
+            }
+            Event::CData(e) => out.push_str(&format!(
+                "{}<![CDATA[{}]]>\n",
+                pad,
+                String::from_utf8_lossy(e)
+            )),
+            Event::Comment(e) => {
+                let comment = format!("<!--{}-->", String::from_utf8_lossy(e));
+
+                out.push_str(&format!("{}{}\n", pad, paint(&comment, KEYWORD, color)));
+            }
+            Event::Decl(e) => {
+                out.push_str(&format!("<?{}?>\n", String::from_utf8_lossy(e)));
+            }
+            Event::PI(e) => out.push_str(&format!("{}<?{}?>\n", pad, String::from_utf8_lossy(e))),
+            Event::DocType(e) => {
+                out.push_str(&format!("<!DOCTYPE {}>\n", String::from_utf8_lossy(e)))
+            }
+            Event::GeneralRef(_) | Event::Eof => {}
+        }
+
+        i += 1;
+    }
+
+    Ok(out.trim_end().to_string())
+}
+
+fn open_tag(e: &quick_xml::events::BytesStart, empty: bool, color: bool) -> String {
+    let mut tag = format!(
+        "<{}",
+        paint(&String::from_utf8_lossy(e.name().as_ref()), KEY, color)
+    );
+
+    for attr in e.attributes().flatten() {
+        tag.push_str(&format!(
+            " {}=\"{}\"",
+            paint(&String::from_utf8_lossy(attr.key.as_ref()), NUMBER, color),
+            paint(&String::from_utf8_lossy(&attr.value), STRING, color)
+        ));
+    }
+
+    tag.push_str(if empty { "/>" } else { ">" });
+
+    tag
+}
+
+fn close_tag(name: &[u8], color: bool) -> String {
+    format!("</{}>", paint(&String::from_utf8_lossy(name), KEY, color))
+}
+
+/// Находит текст элемента или значение атрибута XML по пути от корневого элемента.
+///
+/// Путь `database.password` ищет `<database><password>` внутри корня,
+/// последний шаг `@name` выбирает атрибут, `[n]` - n-й одноименный элемент.
+
+pub fn query_xml(bytes: &[u8], path: &str) -> Result<Option<String>, String> {
+    let mut path = segments(path);
+
+    let attribute = path
+        .last()
+        .and_then(|s| s.key.strip_prefix('@'))
+        .map(String::from);
+
+    if attribute.is_some() {
+        path.pop();
+    }
+
+    // Открытые элементы ниже корня: имя, номер среди одноименных соседей и счетчики детей
+    let mut stack: Vec<(String, usize, std::collections::HashMap<String, usize>)> = Vec::new();
+
+    let mut root_children = std::collections::HashMap::new();
+
+    let mut depth = 0;
+
+    let mut found: Option<String> = None;
+
+    let mut result = None;
+
+    super::xml::walk(bytes, |node| {
+        if result.is_some() {
+            return;
+        }
+
+        match node {
+            super::xml::Node::Start(name, attrs) => {
+                depth += 1;
+
+                if depth == 1 {
+                    if path.is_empty() {
+                        match &attribute {
+                            Some(attribute) => result = Some(attrs.get(attribute).cloned()),
+                            None => found = Some(String::new()),
+                        }
+                    }
+
+                    return;
+                }
+
+                let siblings = stack.last_mut().map_or(&mut root_children, |(_, _, c)| c);
+
+                let counter = siblings.entry(name.to_string()).or_insert(0);
+
+                let index = *counter;
+
+                *counter += 1;
+
+                stack.push((name.to_string(), index, std::collections::HashMap::new()));
+
+                let matches = stack.len() == path.len()
+                    && stack.iter().zip(&path).all(|((name, index, _), segment)| {
+                        *name == segment.key && segment.index.unwrap_or(0) == *index
+                    });
+
+                if matches && found.is_none() {
+                    match &attribute {
+                        Some(attribute) => result = Some(attrs.get(attribute).cloned()),
+                        None => found = Some(String::new()),
+                    }
+                }
+            }
+            super::xml::Node::End(_) => {
+                if found.is_some() && stack.len() == path.len() {
+                    result = Some(found.take().map(|text| text.trim().to_string()));
+                }
+
+                depth -= 1;
+
+                stack.pop();
+            }
+            super::xml::Node::Text(text) => {
+                if let Some(found) = &mut found {
+                    found.push_str(text);
+                }
+            }
+        }
+    })?;
+
+    Ok(result.flatten())
+}
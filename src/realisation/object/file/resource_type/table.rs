@@ -0,0 +1,119 @@
+use calamine::Reader;
+
+/// Лист таблицы: имя и строки ячеек, приведенных к тексту
+
+pub struct Sheet {
+    pub name: String,
+    pub names: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Читает лист электронной таблицы (XLSX, XLS, ODS).
+///
+/// Лист выбирается по имени или номеру, начиная с 1, по умолчанию - первый.
+
+pub fn read_spreadsheet(bytes: &[u8], sheet: Option<&str>) -> Result<Sheet, String> {
+    let mut workbook = calamine::open_workbook_auto_from_rs(std::io::Cursor::new(bytes))
+        .map_err(|e| e.to_string())?;
+
+    let names = workbook.sheet_names();
+
+    let name = match sheet {
+        None => names.first().cloned(),
+        Some(sheet) => names
+            .iter()
+            .find(|n| n.as_str() == sheet)
+            .cloned()
+            .or_else(|| {
+                sheet
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| i.checked_sub(1))
+                    .and_then(|i| names.get(i).cloned())
+            }),
+    }
+    .ok_or_else(|| format!("лист не найден, доступные листы: {}", names.join(", ")))?;
+
+    let range = workbook.worksheet_range(&name).map_err(|e| e.to_string())?;
+
+    let rows = range
+        .rows()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+
+    Ok(Sheet { name, names, rows })
+}
+
+/// Читает текст с разделителями (CSV, TSV), строки могут иметь разную длину
+
+pub fn read_delimited(bytes: &[u8], delimiter: u8) -> Result<Vec<Vec<String>>, String> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(bytes)
+        .byte_records()
+        .map(|record| {
+            record
+                .map(|r| {
+                    r.iter()
+                        .map(|field| String::from_utf8_lossy(field).into_owned())
+                        .collect()
+                })
+                .map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+/// Ограничивает число строк и столбцов таблицы, 0 - без ограничения.
+///
+/// Если часть таблицы скрыта, за ней следует примечание с исходными размерами.
+
+pub fn truncate(
+    mut rows: Vec<Vec<String>>,
+    max_rows: usize,
+    max_cols: usize,
+) -> Vec<crate::abstraction::Block> {
+    let total_rows = rows.len();
+
+    let total_cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    let shown_rows = limit(total_rows, max_rows);
+
+    let shown_cols = limit(total_cols, max_cols);
+
+    rows.truncate(shown_rows);
+
+    rows.iter_mut().for_each(|row| row.truncate(shown_cols));
+
+    let mut blocks = vec![crate::abstraction::Block::Table(rows)];
+
+    if shown_rows < total_rows || shown_cols < total_cols {
+        blocks.push(crate::abstraction::Block::Note(format!(
+            "показано строк: {} из {}, столбцов: {} из {}",
+            shown_rows, total_rows, shown_cols, total_cols
+        )));
+    }
+
+    blocks
+}
+
+/// Строки целиком в формате CSV
+
+pub fn to_csv(rows: &[Vec<String>]) -> Result<String, String> {
+    let mut writer = csv::WriterBuilder::new()
+        .flexible(true)
+        .from_writer(Vec::new());
+
+    for row in rows {
+        writer.write_record(row).map_err(|e| e.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+fn limit(total: usize, max: usize) -> usize {
+    if max == 0 { total } else { total.min(max) }
+}
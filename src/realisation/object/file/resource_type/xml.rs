@@ -0,0 +1,99 @@
+use quick_xml::events::Event;
+
+/// Узел XML документа, имена элементов и атрибутов без префикса пространства имен
+
+#[derive(Clone, Copy)]
+
+pub enum Node<'a> {
+    Start(&'a str, &'a std::collections::HashMap<String, String>),
+    End(&'a str),
+    Text(&'a str),
+}
+
+/// Читает файл из ZIP контейнера документа, `None` - файла нет
+
+pub fn zip_entry(bytes: &[u8], name: &str) -> Result<Option<Vec<u8>>, String> {
+    let mut archive =
+        zip::ZipArchive::new(std::io::Cursor::new(bytes)).map_err(|e| e.to_string())?;
+
+    let mut file = match archive.by_name(name) {
+        Ok(file) => file,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut data = Vec::new();
+
+    std::io::Read::read_to_end(&mut file, &mut data).map_err(|e| e.to_string())?;
+
+    Ok(Some(data))
+}
+
+/// Обходит XML документ по порядку, передавая узлы в `f`.
+///
+/// Пустые элементы передаются парой `Start` и `End`, ссылки на сущности
+/// раскрываются в текст.
+
+pub fn walk<F>(xml: &[u8], mut f: F) -> Result<(), String>
+where
+    F: FnMut(Node),
+{
+    let mut reader = quick_xml::Reader::from_reader(xml);
+
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) => {
+                let name = local_name(e.local_name().as_ref());
+
+                f(Node::Start(&name, &attributes(&e, reader.decoder())));
+            }
+            Event::Empty(e) => {
+                let name = local_name(e.local_name().as_ref());
+
+                f(Node::Start(&name, &attributes(&e, reader.decoder())));
+
+                f(Node::End(&name));
+            }
+            Event::End(e) => f(Node::End(&local_name(e.local_name().as_ref()))),
+            Event::Text(e) => f(Node::Text(&e.decode().map_err(|e| e.to_string())?)),
+            Event::CData(e) => f(Node::Text(&e.decode().map_err(|e| e.to_string())?)),
+            Event::GeneralRef(e) => {
+                let text = match e.resolve_char_ref().map_err(|e| e.to_string())? {
+                    Some(c) => c.to_string(),
+                    None => {
+                        let name = e.decode().map_err(|e| e.to_string())?;
+
+                        quick_xml::escape::resolve_predefined_entity(&name)
+                            .unwrap_or_default()
+                            .to_string()
+                    }
+                };
+
+                f(Node::Text(&text));
+            }
+            Event::Eof => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+fn local_name(name: &[u8]) -> String {
+    String::from_utf8_lossy(name).into_owned()
+}
+
+fn attributes(
+    e: &quick_xml::events::BytesStart,
+    decoder: quick_xml::encoding::Decoder,
+) -> std::collections::HashMap<String, String> {
+    e.attributes()
+        .flatten()
+        .map(|a| {
+            let value = a
+                .decode_and_unescape_value(decoder)
+                .map(|v| v.into_owned())
+                .unwrap_or_default();
+
+            (local_name(a.key.local_name().as_ref()), value)
+        })
+        .collect()
+}
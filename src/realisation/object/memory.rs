@@ -0,0 +1,72 @@
+use crate::abstraction::ResourcePath;
+
+pub mod path;
+
+/// Ресурс, хранящийся в памяти процесса под ключом `mem://name`.
+///
+/// Позволяет прогнать весь конвейер приложения без файловой системы.
+#[derive(Debug)]
+
+pub struct MemoryResource {
+    path: path::MemoryPath,
+    position: usize,
+}
+
+impl crate::abstraction::Reader for MemoryResource {
+    type Error = std::io::Error;
+
+    fn read_to_end(&mut self) -> Result<crate::abstraction::Data, Self::Error> {
+        let store = path::store();
+
+        let data = store.get(self.path.get_path()).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Ресурс '{}' удален из памяти", self.path.get_path()),
+            )
+        })?;
+
+        let rest = data.get(self.position..).unwrap_or_default().to_vec();
+
+        self.position += rest.len();
+
+        Ok(rest.into())
+    }
+}
+
+impl crate::abstraction::Writer for MemoryResource {
+    type Error = std::io::Error;
+
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        path::store()
+            .entry(self.path.get_path().clone())
+            .or_default()
+            .extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+}
+
+impl crate::abstraction::UnifiedResourceIdentifierAbstraction for MemoryResource {
+    type Path = path::MemoryPath;
+
+    type Type = super::file::resource_type::ResourceType;
+
+    type Error = std::io::Error;
+
+    fn new(
+        path: Self::Path,
+        op: crate::abstraction::Operation,
+    ) -> Result<Self, <Self::Path as crate::abstraction::ResourcePath>::Error>
+    where
+        Self: Sized,
+    {
+        match op {
+            crate::abstraction::Operation::Open => {}
+            crate::abstraction::Operation::Create | crate::abstraction::Operation::Truncate => {
+                path::store().insert(path.get_path().clone(), Vec::new());
+            }
+        }
+
+        Ok(MemoryResource { path, position: 0 })
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+/// Префикс, по которому путь распознается как ресурс в памяти.
+
+pub const MEMORY_SCHEME: &str = "mem://";
+
+/// Общее хранилище ресурсов в памяти процесса, ключ - имя без префикса.
+
+static STORE: LazyLock<Mutex<HashMap<String, Vec<u8>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Захват хранилища, отравленный мьютекс не мешает работе с данными
+
+pub(crate) fn store() -> MutexGuard<'static, HashMap<String, Vec<u8>>> {
+    STORE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Помещает данные в хранилище под ключом `mem://name`
+
+pub fn put(name: &str, data: Vec<u8>) {
+    store().insert(name.to_string(), data);
+}
+
+/// Удаляет данные из хранилища и возвращает их
+
+pub fn take(name: &str) -> Option<Vec<u8>> {
+    store().remove(name)
+}
+
+#[derive(Debug, Clone)]
+
+pub struct MemoryPath(String);
+
+impl crate::abstraction::ResourcePath for MemoryPath {
+    type Path = String;
+
+    type Error = std::io::Error;
+
+    fn new(path: String, op: crate::abstraction::Operation) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let name = match path.strip_prefix(MEMORY_SCHEME) {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Путь '{}' не является ресурсом {}", path, MEMORY_SCHEME),
+                ));
+            }
+        };
+
+        match op {
+            crate::abstraction::Operation::Open | crate::abstraction::Operation::Truncate => {
+                if store().contains_key(&name) {
+                    Ok(MemoryPath(name))
+                } else {
+                    Err(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("Ресурса '{}{}' не существует", MEMORY_SCHEME, name),
+                    ))
+                }
+            }
+            crate::abstraction::Operation::Create => Ok(MemoryPath(name)),
+        }
+    }
+
+    fn size(&self) -> usize {
+        store().get(&self.0).map_or(0, Vec::len)
+    }
+
+    fn get_path(&self) -> &Self::Path {
+        &self.0
+    }
+
+    fn metadata(&self) -> Result<Vec<u8>, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    fn set_metadata(&self, _metadata: &[u8]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        self.0.clone()
+    }
+
+    fn same_resource(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+
+    fn check_rename(&self, name: &str) -> Result<(), Self::Error> {
+        if store().contains_key(name) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("Ресурс '{}{}' уже существует", MEMORY_SCHEME, name),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn rename(&self, name: &str) -> Result<Self, Self::Error> {
+        self.check_rename(name)?;
+
+        let mut store = store();
+
+        let data = store.remove(&self.0).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Ресурса '{}{}' не существует", MEMORY_SCHEME, self.0),
+            )
+        })?;
+
+        store.insert(name.to_string(), data);
+
+        Ok(MemoryPath(name.to_string()))
+    }
+}
@@ -0,0 +1,96 @@
+/// Схемы дополнения шифрованных данных, скрывающие точный размер файла
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+
+pub enum PaddingScheme {
+    /// PADMÉ: не более 12% накладных расходов, раскрывает O(log log L) бит длины
+    Padme,
+    /// Округление вверх до ближайшей степени двойки
+    Bucket,
+    /// Округление вверх до кратного размеру блока
+    Block(usize),
+    None,
+}
+
+impl core::fmt::Display for PaddingScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaddingScheme::Padme => write!(f, "padme"),
+            PaddingScheme::Bucket => write!(f, "bucket"),
+            PaddingScheme::Block(size) => write!(f, "block:{}", size),
+            PaddingScheme::None => write!(f, "none"),
+        }
+    }
+}
+
+impl core::str::FromStr for PaddingScheme {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const ERROR: &str =
+            "Invalid padding scheme. Use 'padme', 'bucket', 'block:<bytes>' or 'none'.";
+
+        match s.to_lowercase().as_str() {
+            "padme" => Ok(PaddingScheme::Padme),
+            "bucket" => Ok(PaddingScheme::Bucket),
+            "none" => Ok(PaddingScheme::None),
+            other => match other.strip_prefix("block:").map(str::parse::<usize>) {
+                Some(Ok(size)) if size > 0 => Ok(PaddingScheme::Block(size)),
+                _ => Err(ERROR),
+            },
+        }
+    }
+}
+
+impl crate::abstraction::Padding for PaddingScheme {
+    fn padded_len(&self, len: usize) -> usize {
+        match self {
+            PaddingScheme::Padme => {
+                if len < 2 {
+                    return len;
+                }
+
+                let e = len.ilog2();
+
+                let s = e.ilog2() + 1;
+
+                let mask = (1usize << (e - s)) - 1;
+
+                (len + mask) & !mask
+            }
+            PaddingScheme::Bucket => len.next_power_of_two(),
+            PaddingScheme::Block(size) => len.div_ceil(*size) * size,
+            PaddingScheme::None => len,
+        }
+    }
+
+    /// Вид схемы, 1 байт, у `Block` за ним размер блока, 8 байт
+
+    fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            PaddingScheme::None => vec![0],
+            PaddingScheme::Padme => vec![1],
+            PaddingScheme::Bucket => vec![2],
+            PaddingScheme::Block(size) => {
+                let mut bytes = vec![3];
+
+                bytes.extend_from_slice(&(*size as u64).to_le_bytes());
+
+                bytes
+            }
+        }
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(PaddingScheme::None),
+            [1] => Some(PaddingScheme::Padme),
+            [2] => Some(PaddingScheme::Bucket),
+            [3, size @ ..] => {
+                let size = usize::try_from(u64::from_le_bytes(size.try_into().ok()?)).ok()?;
+
+                (size > 0).then_some(PaddingScheme::Block(size))
+            }
+            _ => None,
+        }
+    }
+}